@@ -0,0 +1,144 @@
+use crate::{sort::NameOrder, style::Catalog, FileTreeEvent, Icon, IconProvider, LINE_HEIGHT};
+use iced::{advanced::svg::Handle, Font, Length, Pixels};
+use std::{
+    collections::HashSet,
+    fs::Metadata,
+    path::{Path, PathBuf},
+};
+
+const DIR_CLOSED: &[u8] = include_bytes!("../assets/system-uicons--chevron-right.svg");
+const DIR_OPEN: &[u8] = include_bytes!("../assets/system-uicons--chevron-down.svg");
+
+/// Which indentation guides a [`FileTree`](crate::FileTree) draws below open directories.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Guides {
+    /// No guides are drawn.
+    Hidden,
+    /// Only the guide of the directory containing the hovered or selected row is drawn.
+    Active,
+    /// The guides of all open directories are drawn, at every depth level.
+    #[default]
+    All,
+    /// The guides of all open directories are drawn, and the guide of the directory containing
+    /// the hovered or selected row is highlighted, like in VS Code.
+    Highlighted,
+}
+
+/// Which clicks on a directory of a [`FileTree`](crate::FileTree) open or close it.
+///
+/// Clicking the chevron in front of a directory always opens or closes it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ToggleOn {
+    /// A single click anywhere on the row.
+    #[default]
+    Click,
+    /// A double click anywhere on the row.
+    DoubleClick,
+    /// Only a click on the chevron.
+    Chevron,
+}
+
+/// How a [`FileTree`](crate::FileTree) shortens names that don't fit in their row.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Truncation {
+    /// The name is cut off at the edge of the row.
+    Clip,
+    /// The end of the name is replaced with an ellipsis.
+    #[default]
+    End,
+    /// The middle of the name is replaced with an ellipsis, keeping its extension, like
+    /// `very_long_na…_report.pdf`.
+    Middle,
+}
+
+/// The settings that decide which rows a [`FileTree`](crate::FileTree) has, so its rows have to
+/// be built again when they change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RowOptions {
+    pub show_hidden: bool,
+    pub show_extensions: bool,
+    /// Whether chains of directories that only contain another directory share a row.
+    pub compact_folders: bool,
+}
+
+impl Default for RowOptions {
+    fn default() -> Self {
+        Self {
+            show_hidden: false,
+            show_extensions: true,
+            compact_folders: false,
+        }
+    }
+}
+
+/// Produces the text of the tooltip of an entry.
+pub type TooltipFn<'a> = Box<dyn Fn(&Path, &Metadata) -> String + 'a>;
+
+/// The settings of a [`FileTree`](crate::FileTree).
+pub struct Config<'a, Message, Theme>
+where
+    Theme: Catalog,
+{
+    pub on_single_click: Option<Box<dyn Fn(PathBuf) -> Message + 'a>>,
+    pub on_double_click: Option<Box<dyn Fn(PathBuf) -> Message + 'a>>,
+    pub on_toggle: Option<Box<dyn Fn(PathBuf, bool) -> Message + 'a>>,
+    pub on_event: Option<Box<dyn Fn(FileTreeEvent) -> Message + 'a>>,
+    pub on_hover: Option<Box<dyn Fn(Option<PathBuf>) -> Message + 'a>>,
+    pub tooltip: Option<TooltipFn<'a>>,
+    pub expanded: Option<&'a HashSet<PathBuf>>,
+    pub toggle_on: ToggleOn,
+    pub icons: Option<Box<dyn IconProvider + 'a>>,
+    pub chevron_closed: Icon,
+    pub chevron_open: Icon,
+    pub folder_icons: bool,
+    pub icon_font: Option<Font>,
+    pub width: Length,
+    pub row_height: f32,
+    pub indent: f32,
+    pub text_size: Option<Pixels>,
+    pub font: Option<Font>,
+    pub dir_font: Option<Font>,
+    pub guides: Guides,
+    pub truncation: Truncation,
+    pub decoration_width: f32,
+    pub rows: RowOptions,
+    pub order: NameOrder,
+    pub class: <Theme as Catalog>::Class<'a>,
+    pub tooltip_class: <Theme as Catalog>::TooltipClass<'a>,
+}
+
+impl<Message, Theme> Default for Config<'_, Message, Theme>
+where
+    Theme: Catalog,
+{
+    fn default() -> Self {
+        Self {
+            on_single_click: None,
+            on_double_click: None,
+            on_toggle: None,
+            on_event: None,
+            on_hover: None,
+            tooltip: None,
+            expanded: None,
+            toggle_on: ToggleOn::default(),
+            icons: None,
+            chevron_closed: Icon::Svg(Handle::from_memory(DIR_CLOSED)),
+            chevron_open: Icon::Svg(Handle::from_memory(DIR_OPEN)),
+            folder_icons: false,
+            icon_font: None,
+            width: Length::Fill,
+            row_height: LINE_HEIGHT,
+            indent: LINE_HEIGHT,
+            text_size: None,
+            font: None,
+            dir_font: None,
+            guides: Guides::default(),
+            truncation: Truncation::default(),
+            decoration_width: 0.0,
+            rows: RowOptions::default(),
+            order: NameOrder::default(),
+            class: <Theme as Catalog>::default(),
+            tooltip_class: <Theme as Catalog>::default_tooltip(),
+        }
+    }
+}
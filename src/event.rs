@@ -0,0 +1,115 @@
+use iced::{
+    advanced::mouse::{self, click},
+    keyboard::Modifiers,
+    Point,
+};
+use std::path::{Path, PathBuf};
+
+/// Whether an entry of a [`FileTree`](crate::FileTree) is a file or a directory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EntryKind {
+    /// A file.
+    File,
+    /// A directory.
+    Directory,
+}
+
+/// An interaction with an entry of a [`FileTree`](crate::FileTree), see
+/// [`FileTree::on_event`](crate::FileTree::on_event).
+#[derive(Clone, Debug, PartialEq)]
+pub enum FileTreeEvent {
+    /// The entry was clicked.
+    Clicked {
+        path: PathBuf,
+        kind: EntryKind,
+        button: mouse::Button,
+        modifiers: Modifiers,
+        /// The number of clicks in quick succession, up to three.
+        count: usize,
+    },
+    /// The directory was opened or closed.
+    Toggled {
+        path: PathBuf,
+        kind: EntryKind,
+        /// Whether the directory is now open.
+        open: bool,
+    },
+    /// The cursor entered the row of the entry.
+    Hovered { path: PathBuf, kind: EntryKind },
+    /// The cursor left the row of the entry.
+    Unhovered { path: PathBuf, kind: EntryKind },
+    /// The entry became the selected one.
+    SelectionChanged { path: PathBuf, kind: EntryKind },
+    /// The entry was activated, either with the keyboard while it had focus or with a double
+    /// tap.
+    Activated { path: PathBuf, kind: EntryKind },
+    /// The user asked to rename the focused entry.
+    RenameRequested { path: PathBuf, kind: EntryKind },
+    /// A file was dropped onto the entry from outside the application.
+    Dropped {
+        path: PathBuf,
+        kind: EntryKind,
+        /// The path of the dropped file.
+        dropped: PathBuf,
+    },
+    /// The entry was dragged onto another directory of the tree, either with the mouse or after
+    /// holding it down with a finger.
+    Moved {
+        path: PathBuf,
+        kind: EntryKind,
+        /// The path of the directory the entry was dragged onto.
+        target: PathBuf,
+    },
+    /// The user asked for a context menu of the entry.
+    ContextRequested {
+        path: PathBuf,
+        kind: EntryKind,
+        /// Where the menu was requested, if it was requested with the mouse.
+        position: Option<Point>,
+    },
+}
+
+impl FileTreeEvent {
+    /// Returns the path of the entry the event is about.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Clicked { path, .. }
+            | Self::Toggled { path, .. }
+            | Self::Hovered { path, .. }
+            | Self::Unhovered { path, .. }
+            | Self::SelectionChanged { path, .. }
+            | Self::Activated { path, .. }
+            | Self::RenameRequested { path, .. }
+            | Self::Dropped { path, .. }
+            | Self::Moved { path, .. }
+            | Self::ContextRequested { path, .. } => path,
+        }
+    }
+
+    /// Returns the [`EntryKind`] of the entry the event is about.
+    #[must_use]
+    pub fn kind(&self) -> EntryKind {
+        match self {
+            Self::Clicked { kind, .. }
+            | Self::Toggled { kind, .. }
+            | Self::Hovered { kind, .. }
+            | Self::Unhovered { kind, .. }
+            | Self::SelectionChanged { kind, .. }
+            | Self::Activated { kind, .. }
+            | Self::RenameRequested { kind, .. }
+            | Self::Dropped { kind, .. }
+            | Self::Moved { kind, .. }
+            | Self::ContextRequested { kind, .. } => *kind,
+        }
+    }
+}
+
+/// Returns the number of clicks of a [`click::Kind`].
+pub fn click_count(kind: click::Kind) -> usize {
+    match kind {
+        click::Kind::Single => 1,
+        click::Kind::Double => 2,
+        click::Kind::Triple => 3,
+    }
+}
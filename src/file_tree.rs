@@ -1,34 +1,96 @@
-use crate::dir::Dir;
+use crate::{
+    config::Config,
+    event::click_count,
+    icon,
+    state::{Drag, Entry, Gesture, State, Tap, Touch, SEPARATOR},
+    style::{self, Catalog, Style, StyleFn, TooltipStyle, TooltipStyleFn},
+    tooltip::Tooltip,
+    truncate::measure,
+    EntryKind, FileTreeEvent, Guides, Icon, IconProvider, ToggleOn, Truncation, LINE_HEIGHT,
+};
+#[cfg(feature = "collation")]
+use crate::{sort::NameOrder, Locale};
 use iced::{
     advanced::{
         layout::{Limits, Node},
-        renderer::Style,
-        widget::{tree, Tree},
-        Clipboard, Layout, Renderer as _, Shell, Widget,
+        mouse::{self, Click, Cursor},
+        overlay,
+        renderer::{self, Quad},
+        svg::{self, Handle},
+        text::{self, LineHeight, Shaping, Wrapping},
+        widget::{operation::Focusable as _, tree, Id, Operation, Tree},
+        Clipboard, Layout, Shell, Text, Widget,
     },
+    alignment::{Horizontal, Vertical},
     event::Status,
-    mouse::Cursor,
-    Element, Event, Length, Rectangle, Renderer, Size, Theme,
+    keyboard::{self, key, Key, Modifiers},
+    time::{Duration, Instant},
+    touch,
+    window::{self, RedrawRequest},
+    Background, Color, Element, Event, Font, Length, Pixels, Point, Rectangle, Size, Vector,
 };
 use std::{
+    collections::HashSet,
     fmt::{Debug, Formatter},
-    path::PathBuf,
+    fs::Metadata,
+    ops::Range,
+    path::{Path, PathBuf},
+    rc::Rc,
 };
 
+const FILE: &[u8] = include_bytes!("../assets/system-uicons--document.svg");
+const FOLDER: &[u8] = include_bytes!("../assets/icons/folder.svg");
+const FOLDER_OPEN: &[u8] = include_bytes!("../assets/icons/folder-open.svg");
+
+/// How long a finger has to be held down on an entry to request a context menu.
+const LONG_PRESS: Duration = Duration::from_millis(500);
+/// How far a finger can move before a touch stops being a tap.
+const TOUCH_SLOP: f32 = 8.0;
+/// How soon after a tap another one has to follow to make a double tap.
+const DOUBLE_TAP: Duration = Duration::from_millis(300);
+/// How far the cursor has to move with the button held down before an entry is dragged.
+const DRAG_DISTANCE: f32 = 4.0;
+/// How long an entry has to be hovered before its tooltip is shown.
+const TOOLTIP_DELAY: Duration = Duration::from_millis(700);
+
+/// What pressed a row.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Pointer {
+    Mouse(mouse::Button),
+    /// A tap, which counts as a left click of the given count.
+    Finger {
+        count: usize,
+    },
+}
+
+impl Pointer {
+    const fn button(self) -> mouse::Button {
+        match self {
+            Self::Mouse(button) => button,
+            Self::Finger { .. } => mouse::Button::Left,
+        }
+    }
+}
+
 /// A lightweight file tree widget for the [iced](https://github.com/iced-rs/iced/tree/master) toolkit.
 ///
 /// # Example
 /// ```no_run
+/// # use iced::Element;
+/// # use std::path::PathBuf;
 /// use iced::widget::scrollable;
 /// use iced_file_tree::file_tree;
 ///
+/// #[derive(Clone)]
 /// enum Message {
 ///     FileTreeMessage(PathBuf),
 ///     // ...
 /// }
+/// # struct State { path: PathBuf }
 ///
 /// fn view(state: &State) -> Element<'_, Message> {
 ///     let path: PathBuf = // ...
+/// #       state.path.clone();
 ///
 ///     scrollable(
 ///         file_tree(path)
@@ -37,88 +99,1290 @@ use std::{
 ///     .into()
 /// }
 /// ```
-pub struct FileTree<Message>(Dir<Message>);
+pub struct FileTree<'a, Message, Theme = iced::Theme>
+where
+    Theme: Catalog,
+{
+    id: Option<Id>,
+    path: PathBuf,
+    config: Config<'a, Message, Theme>,
+}
 
-impl<Message> Debug for FileTree<Message> {
+impl<Message, Theme> Debug for FileTree<'_, Message, Theme>
+where
+    Theme: Catalog,
+{
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Dir")
-            .field("path", &self.0.path)
-            .field("show_hidden", &self.0.show_hidden)
-            .field("show_extensions", &self.0.show_hidden)
+        f.debug_struct("FileTree")
+            .field("path", &self.path)
+            .field("show_hidden", &self.config.rows.show_hidden)
+            .field("show_extensions", &self.config.rows.show_extensions)
             .finish()
     }
 }
 
 /// Creates a new [`FileTree`] with the root at the given path.
 #[must_use]
-pub fn file_tree<Message>(path: PathBuf) -> FileTree<Message>
+pub fn file_tree<'a, Message, Theme>(path: PathBuf) -> FileTree<'a, Message, Theme>
 where
     Message: Clone + 'static,
+    Theme: Catalog + 'static,
 {
-    FileTree::<Message>::new(path)
+    FileTree::new(path)
 }
 
-impl<Message> FileTree<Message>
+impl<'a, Message, Theme> FileTree<'a, Message, Theme>
 where
     Message: Clone + 'static,
+    Theme: Catalog + 'static,
 {
     /// Creates a new [`FileTree`] with the root at the given path.
     #[must_use]
     pub fn new(path: PathBuf) -> Self {
-        Self(Dir::new_inner(path, None, None, false, true))
+        debug_assert!(path.is_dir());
+
+        Self {
+            id: None,
+            path,
+            config: Config::default(),
+        }
+    }
+
+    /// Sets the [`Id`] of the [`FileTree`], which is needed to operate on it.
+    #[must_use]
+    pub fn id(mut self, id: impl Into<Id>) -> Self {
+        self.id = Some(id.into());
+        self
     }
 
     /// Sets the message that will be produced when the user single-clicks on a file within the [`FileTree`].
+    ///
+    /// Only left clicks produce this message. Use [`on_event`](Self::on_event) to get the mouse
+    /// button and modifiers of every click.
     #[must_use]
-    pub fn on_single_click(mut self, on_single_click: fn(PathBuf) -> Message) -> Self {
-        self.0.on_single_click.replace(on_single_click);
+    pub fn on_single_click(mut self, on_single_click: impl Fn(PathBuf) -> Message + 'a) -> Self {
+        self.config.on_single_click = Some(Box::new(on_single_click));
         self
     }
 
     /// Sets the message that will be produced when the user double-clicks on a file within the [`FileTree`].
+    ///
+    /// Only left clicks produce this message. Use [`on_event`](Self::on_event) to get the mouse
+    /// button and modifiers of every click.
     #[must_use]
-    pub fn on_double_click(mut self, on_double_click: fn(PathBuf) -> Message) -> Self {
-        self.0.on_double_click.replace(on_double_click);
+    pub fn on_double_click(mut self, on_double_click: impl Fn(PathBuf) -> Message + 'a) -> Self {
+        self.config.on_double_click = Some(Box::new(on_double_click));
+        self
+    }
+
+    /// Sets the message that will be produced when the user opens or closes a directory within
+    /// the [`FileTree`], with its path and whether it's now open.
+    #[must_use]
+    pub fn on_toggle(mut self, on_toggle: impl Fn(PathBuf, bool) -> Message + 'a) -> Self {
+        self.config.on_toggle = Some(Box::new(on_toggle));
+        self
+    }
+
+    /// Sets the message that will be produced for every [`FileTreeEvent`] within the [`FileTree`].
+    ///
+    /// It's produced in addition to the more specific messages, like
+    /// [`on_double_click`](Self::on_double_click).
+    #[must_use]
+    pub fn on_event(mut self, on_event: impl Fn(FileTreeEvent) -> Message + 'a) -> Self {
+        self.config.on_event = Some(Box::new(on_event));
+        self
+    }
+
+    /// Sets the message that will be produced when the cursor enters a row of the [`FileTree`],
+    /// with the path of its entry, or leaves the rows, with [`None`].
+    #[must_use]
+    pub fn on_hover(mut self, on_hover: impl Fn(Option<PathBuf>) -> Message + 'a) -> Self {
+        self.config.on_hover = Some(Box::new(on_hover));
+        self
+    }
+
+    /// Shows a tooltip after a row has been hovered for a moment, with the text produced from
+    /// the path and [`Metadata`] of its entry.
+    ///
+    /// Use [`describe`](crate::describe) to show the full path, size, modification time and
+    /// permissions of the entry.
+    #[must_use]
+    pub fn tooltip(mut self, tooltip: impl Fn(&Path, &Metadata) -> String + 'a) -> Self {
+        self.config.tooltip = Some(Box::new(tooltip));
+        self
+    }
+
+    /// Makes the application responsible for which directories are open.
+    ///
+    /// Only the directories in the given set are open, and clicking a directory doesn't open or
+    /// close it by itself. Use [`on_toggle`](Self::on_toggle) to update the set instead.
+    #[must_use]
+    pub fn expanded(mut self, expanded: &'a HashSet<PathBuf>) -> Self {
+        self.config.expanded.replace(expanded);
+        self
+    }
+
+    /// Sets which clicks on a directory open or close it ([`ToggleOn::Click`] by default).
+    #[must_use]
+    pub fn toggle_on(mut self, toggle_on: ToggleOn) -> Self {
+        self.config.toggle_on = toggle_on;
+        self
+    }
+
+    /// Sets the [`IconProvider`] that decides which icon is drawn for each entry.
+    ///
+    /// The [`FileTree`] is built again in every `view`, so providers that cache what they look up
+    /// should be kept in the application state and passed by reference or in an [`Rc`].
+    #[must_use]
+    pub fn icons(mut self, icons: impl IconProvider + 'a) -> Self {
+        self.config.icons.replace(Box::new(icons));
+        self
+    }
+
+    /// Enables or disables drawing a folder icon after the chevron of each directory (disabled by
+    /// default).
+    ///
+    /// Directories use the icon of the [`IconProvider`] if it has one, and a plain folder icon
+    /// otherwise.
+    #[must_use]
+    pub fn folder_icons(mut self, folder_icons: bool) -> Self {
+        self.config.folder_icons = folder_icons;
+        self
+    }
+
+    /// Sets the font used to draw [`Icon::Glyph`]s (the default font of the renderer by default).
+    #[must_use]
+    pub fn icon_font(mut self, font: Font) -> Self {
+        self.config.icon_font = Some(font);
+        self
+    }
+
+    /// Sets the width of the [`FileTree`] ([`Length::Fill`] by default).
+    ///
+    /// With [`Length::Shrink`], the [`FileTree`] is as wide as its deepest and longest names, so
+    /// it can be placed in a horizontally scrollable [`Scrollable`](iced::widget::Scrollable)
+    /// instead of shortening them.
+    #[must_use]
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.config.width = width.into();
+        self
+    }
+
+    /// Sets the height of each row of the [`FileTree`] (21 pixels by default).
+    ///
+    /// SVG icons and chevrons are scaled to fit the row.
+    #[must_use]
+    pub fn row_height(mut self, row_height: impl Into<Pixels>) -> Self {
+        self.config.row_height = row_height.into().0;
+        self
+    }
+
+    /// Sets how far the entries of a directory are indented (21 pixels by default).
+    #[must_use]
+    pub fn indent(mut self, indent: impl Into<Pixels>) -> Self {
+        self.config.indent = indent.into().0;
+        self
+    }
+
+    /// Sets the size of the names and [`Icon::Glyph`]s (the default text size of the renderer by
+    /// default).
+    #[must_use]
+    pub fn text_size(mut self, text_size: impl Into<Pixels>) -> Self {
+        self.config.text_size = Some(text_size.into());
+        self
+    }
+
+    /// Sets the font of the names (the default font of the renderer by default).
+    #[must_use]
+    pub fn font(mut self, font: Font) -> Self {
+        self.config.font = Some(font);
+        self
+    }
+
+    /// Sets a separate font for the names of directories, e.g. a bold variant of the
+    /// [`font`](Self::font).
+    #[must_use]
+    pub fn dir_font(mut self, font: Font) -> Self {
+        self.config.dir_font = Some(font);
+        self
+    }
+
+    /// Sets which indentation guides are drawn below open directories ([`Guides::All`] by
+    /// default).
+    #[must_use]
+    pub fn guides(mut self, guides: Guides) -> Self {
+        self.config.guides = guides;
+        self
+    }
+
+    /// Sets how names that don't fit in their row are shortened ([`Truncation::End`] by default).
+    #[must_use]
+    pub fn truncation(mut self, truncation: Truncation) -> Self {
+        self.config.truncation = truncation;
+        self
+    }
+
+    /// Reserves space at the end of each row for decorations drawn by the application, like
+    /// version control badges, so names are shortened before they reach it.
+    #[must_use]
+    pub fn decoration_width(mut self, decoration_width: impl Into<Pixels>) -> Self {
+        self.config.decoration_width = decoration_width.into().0;
+        self
+    }
+
+    /// Sets the icons drawn in front of closed and open directories, replacing the default
+    /// chevrons.
+    ///
+    /// Empty directories never draw a chevron.
+    #[must_use]
+    pub fn chevrons(mut self, closed: Icon, open: Icon) -> Self {
+        let config = &mut self.config;
+        config.chevron_closed = closed;
+        config.chevron_open = open;
         self
     }
 
     /// Enables or disables showing hidden files (disabled by default).
     #[must_use]
     pub fn hidden_files(mut self, show_hidden: bool) -> Self {
-        self.0.show_hidden = show_hidden;
+        self.config.rows.show_hidden = show_hidden;
         self
     }
 
     #[must_use]
     /// Enables or disables showing file extensions (enabled by default).
     pub fn file_extensions(mut self, show_extensions: bool) -> Self {
-        self.0.show_extensions = show_extensions;
+        self.config.rows.show_extensions = show_extensions;
+        self
+    }
+
+    /// Enables or disables showing chains of directories that only contain another directory in
+    /// a single row, like `src/main/java` (disabled by default).
+    ///
+    /// Each directory in the chain can be clicked on its own, and the whole chain is opened and
+    /// closed at once. Chains that don't fit in their row are clipped instead of truncated.
+    #[must_use]
+    pub fn compact_folders(mut self, compact_folders: bool) -> Self {
+        self.config.rows.compact_folders = compact_folders;
+        self
+    }
+
+    /// Sets the style of the [`FileTree`].
+    #[must_use]
+    pub fn style(mut self, style: impl Fn(&Theme, style::Status) -> Style + 'a) -> Self
+    where
+        Theme::Class<'a>: From<StyleFn<'a, Theme>>,
+    {
+        self.config.class = (Box::new(style) as StyleFn<'a, Theme>).into();
+        self
+    }
+
+    /// Sets the style class of the [`FileTree`].
+    #[must_use]
+    pub fn class(mut self, class: impl Into<Theme::Class<'a>>) -> Self {
+        self.config.class = class.into();
+        self
+    }
+
+    /// Sets the style of the tooltip of the [`FileTree`], see [`FileTree::tooltip`].
+    #[must_use]
+    pub fn tooltip_style(mut self, style: impl Fn(&Theme) -> TooltipStyle + 'a) -> Self
+    where
+        Theme::TooltipClass<'a>: From<TooltipStyleFn<'a, Theme>>,
+    {
+        self.config.tooltip_class = (Box::new(style) as TooltipStyleFn<'a, Theme>).into();
+        self
+    }
+
+    /// Sets the style class of the tooltip of the [`FileTree`].
+    #[must_use]
+    pub fn tooltip_class(mut self, class: impl Into<Theme::TooltipClass<'a>>) -> Self {
+        self.config.tooltip_class = class.into();
+        self
+    }
+
+    /// Sorts entries using the collation rules of the given locale instead of the default
+    /// case-insensitive ordering.
+    ///
+    /// Keeps the default ordering if no collation data is available for the locale.
+    #[cfg(feature = "collation")]
+    #[must_use]
+    pub fn collation(mut self, locale: Locale) -> Self {
+        if let Some(order) = NameOrder::collated(locale) {
+            self.config.order = order;
+        }
         self
     }
 }
 
-impl<Message> Widget<Message, Theme, Renderer> for FileTree<Message>
+impl<Message, Theme> FileTree<'_, Message, Theme>
 where
-    Message: Clone + 'static,
+    Theme: Catalog,
 {
-    fn children(&self) -> Vec<Tree> {
-        self.0.children()
+    /// Returns the range of rows intersecting the viewport.
+    fn visible_rows(&self, state: &State, bounds: Rectangle, viewport: &Rectangle) -> Range<usize> {
+        let row_height = self.config.row_height;
+        let first = ((viewport.y - bounds.y) / row_height).floor().max(0.0) as usize;
+        let last = ((viewport.y + viewport.height - bounds.y) / row_height).ceil() as usize;
+
+        first.min(state.rows().len())..last.min(state.rows().len())
+    }
+
+    /// Returns the row below the cursor, if there is one.
+    fn hovered_row(&self, state: &State, bounds: Rectangle, cursor: Cursor) -> Option<usize> {
+        let pos = cursor.position_in(bounds)?;
+        let row = (pos.y / self.config.row_height) as usize;
+
+        (row < state.rows().len()).then_some(row)
+    }
+
+    /// Returns the bounds of a row, starting at the indentation of its entry.
+    fn row_bounds(&self, state: &State, bounds: Rectangle, row: usize) -> Rectangle {
+        let x = state.rows()[row].depth as f32 * self.config.indent;
+
+        Rectangle::new(
+            bounds.position() + Vector::new(x, row as f32 * self.config.row_height),
+            Size::new(bounds.width - x, self.config.row_height),
+        )
+    }
+
+    fn draw_row<Renderer>(
+        &self,
+        state: &State,
+        row: usize,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        status: style::Status,
+    ) where
+        Renderer: text::Renderer<Font = Font> + svg::Renderer,
+    {
+        let entry = state.entry(row);
+        let bounds = self.row_bounds(state, bounds, row);
+        let style = theme.style(&self.config.class, status);
+
+        let background = Quad {
+            bounds,
+            border: style.border,
+            ..Quad::default()
+        };
+
+        renderer.fill_quad(
+            background,
+            style
+                .background
+                .unwrap_or(Background::Color(Color::TRANSPARENT)),
+        );
+
+        let x = if entry.is_dir {
+            self.draw_dir_icons(state, row, renderer, bounds.position(), style.icon_color)
+        } else {
+            self.draw_file_icon(entry, renderer, bounds.position(), style.icon_color)
+        };
+
+        // the name stays clear of the decorations at the end of the row
+        let clip = Rectangle {
+            width: (bounds.width - self.config.decoration_width).max(0.0),
+            ..bounds
+        };
+
+        let font = self.resolve_name_font(entry, renderer);
+        let size = self.resolve_text_size(renderer);
+        let (truncation, max_width) = (self.config.truncation, clip.width - x);
+
+        // chains of compact folders are clipped, so each directory is drawn where it's hit-tested
+        let content = if state.rows()[row].chain.is_empty() {
+            entry.truncated_name::<Renderer>(font, size, truncation, max_width)
+        } else {
+            state.rows()[row].name().into_owned()
+        };
+
+        renderer.fill_text(
+            Text {
+                content,
+                bounds: Size::new(f32::INFINITY, bounds.height),
+                size,
+                line_height: LineHeight::default(),
+                font,
+                horizontal_alignment: Horizontal::Left,
+                vertical_alignment: Vertical::Center,
+                shaping: Shaping::Advanced,
+                wrapping: Wrapping::None,
+            },
+            bounds.position() + Vector::new(x, bounds.height / 2.0),
+            style.text_color,
+            clip,
+        );
+    }
+
+    /// Draws the icon of a file, returning the horizontal offset of the name.
+    fn draw_file_icon<Renderer>(
+        &self,
+        entry: &Entry,
+        renderer: &mut Renderer,
+        position: Point,
+        color: Color,
+    ) -> f32
+    where
+        Renderer: text::Renderer<Font = Font> + svg::Renderer,
+    {
+        let row_height = self.config.row_height;
+
+        let icon = self
+            .config
+            .icons
+            .as_ref()
+            .and_then(|icons| icons.file_icon(&entry.path))
+            .unwrap_or_else(|| Icon::Svg(Handle::from_memory(FILE)));
+
+        icon::draw(
+            icon,
+            renderer,
+            Rectangle::new(position, Size::new(row_height, row_height)),
+            color,
+            self.resolve_icon_font(renderer),
+            self.resolve_text_size(renderer),
+        );
+
+        row_height
+    }
+
+    /// Draws the chevron and the folder icon of a directory, returning the horizontal offset of
+    /// the name.
+    fn draw_dir_icons<Renderer>(
+        &self,
+        state: &State,
+        row: usize,
+        renderer: &mut Renderer,
+        position: Point,
+        color: Color,
+    ) -> f32
+    where
+        Renderer: text::Renderer<Font = Font> + svg::Renderer,
+    {
+        let entry = state.entry(row);
+        let open = state.is_open(row);
+        let row_height = self.config.row_height;
+        let text_size = self.resolve_text_size(renderer);
+        let icon_font = self.resolve_icon_font(renderer);
+
+        let chevron = if entry.is_empty() {
+            None
+        } else if open {
+            Some(&self.config.chevron_open)
+        } else {
+            Some(&self.config.chevron_closed)
+        };
+
+        if let Some(chevron) = chevron {
+            icon::draw(
+                chevron.clone(),
+                renderer,
+                // the chevron SVGs have a lot of padding, so they're drawn slightly larger
+                Rectangle::new(position, Size::new(row_height, row_height))
+                    .expand(row_height * 2.0 / LINE_HEIGHT),
+                color,
+                icon_font,
+                text_size,
+            );
+        }
+
+        let mut x = row_height;
+
+        if let Some(icon) = self.folder_icon(entry, open) {
+            icon::draw(
+                icon,
+                renderer,
+                Rectangle::new(
+                    position + Vector::new(x, 0.0),
+                    Size::new(row_height, row_height),
+                ),
+                color,
+                icon_font,
+                text_size,
+            );
+
+            x += row_height;
+        }
+
+        x
+    }
+
+    fn folder_icon(&self, entry: &Entry, open: bool) -> Option<Icon> {
+        self.config.folder_icons.then(|| {
+            self.config
+                .icons
+                .as_ref()
+                .and_then(|icons| icons.dir_icon(&entry.path, open))
+                .unwrap_or_else(|| {
+                    Icon::Svg(Handle::from_memory(if open { FOLDER_OPEN } else { FOLDER }))
+                })
+        })
+    }
+
+    /// Returns the horizontal offset of the name in a row, after its icons.
+    fn name_offset(&self, state: &State, row: usize) -> f32 {
+        let entry = state.entry(row);
+
+        if entry.is_dir && self.folder_icon(entry, state.is_open(row)).is_some() {
+            2.0 * self.config.row_height
+        } else {
+            self.config.row_height
+        }
+    }
+
+    /// Returns the entry of the segment of a row at the given horizontal position, which is the
+    /// entry of the row unless it's a chain of compact folders.
+    fn segment_at<Renderer>(
+        &self,
+        state: &State,
+        bounds: Rectangle,
+        row: usize,
+        x: f32,
+        renderer: &Renderer,
+    ) -> Rc<Entry>
+    where
+        Renderer: text::Renderer<Font = Font>,
+    {
+        let entry = &state.rows()[row].entry;
+        let font = self.resolve_name_font(entry, renderer);
+        let text_size = self.resolve_text_size(renderer);
+        let separator = measure::<Renderer>(SEPARATOR, font, text_size);
+
+        let mut end = self.row_bounds(state, bounds, row).x + self.name_offset(state, row);
+
+        for segment in &state.rows()[row].chain {
+            end += segment.name_width::<Renderer>(font, text_size) + separator;
+
+            if x < end {
+                return segment.clone();
+            }
+        }
+
+        entry.clone()
+    }
+
+    /// Draws the indentation guides of the open directories intersecting the visible rows,
+    /// highlighting the guide of the directory in the active row.
+    fn draw_guides<Renderer>(
+        &self,
+        state: &State,
+        rows: Range<usize>,
+        active: Option<usize>,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+    ) where
+        Renderer: text::Renderer,
+    {
+        if self.config.guides == Guides::Hidden || rows.is_empty() {
+            return;
+        }
+
+        let row_height = self.config.row_height;
+        let style = theme.style(&self.config.class, style::Status::Idle);
+
+        // the directories above the first visible row have guides reaching into the viewport
+        let ancestors = std::iter::successors(state.rows()[rows.start].parent, |&row| {
+            state.rows()[row].parent
+        });
+
+        for row in ancestors.chain(rows) {
+            let end = state.rows()[row].end;
+
+            if end == row + 1 {
+                continue;
+            }
+
+            let color = match self.config.guides {
+                Guides::Active | Guides::Highlighted if active == Some(row) => {
+                    style.active_guide_color
+                }
+                Guides::All | Guides::Highlighted => style.guide_color,
+                Guides::Hidden | Guides::Active => continue,
+            };
+
+            let position = self.row_bounds(state, bounds, row).position()
+                + Vector::new(row_height.mul_add(0.5, -1.0), row_height.mul_add(1.5, -1.0));
+            let height = ((end - row - 2) as f32).mul_add(row_height, 2.0);
+            let line = Quad {
+                bounds: Rectangle::new(position, Size::new(2.0, height)),
+                ..Default::default()
+            };
+
+            renderer.fill_quad(line, color);
+        }
+    }
+
+    fn resolve_text_size<Renderer>(&self, renderer: &Renderer) -> Pixels
+    where
+        Renderer: text::Renderer,
+    {
+        self.config
+            .text_size
+            .unwrap_or_else(|| renderer.default_size())
     }
 
+    fn resolve_name_font<Renderer>(&self, entry: &Entry, renderer: &Renderer) -> Font
+    where
+        Renderer: text::Renderer<Font = Font>,
+    {
+        let font = if entry.is_dir {
+            self.config.dir_font.or(self.config.font)
+        } else {
+            self.config.font
+        };
+
+        font.unwrap_or_else(|| renderer.default_font())
+    }
+
+    /// Returns the width the rows need to show every name in full, at their indentation and
+    /// after their icons.
+    fn intrinsic_width<Renderer>(&self, state: &State, renderer: &Renderer) -> f32
+    where
+        Renderer: text::Renderer<Font = Font>,
+    {
+        state
+            .rows()
+            .iter()
+            .enumerate()
+            .map(|(index, row)| {
+                (row.depth as f32).mul_add(
+                    self.config.indent,
+                    self.name_offset(state, index) + self.name_width(state, index, renderer),
+                )
+            })
+            .fold(0.0, f32::max)
+            + self.config.decoration_width
+    }
+
+    /// Returns the width of the name shown in a row, including the chain of compact folders.
+    fn name_width<Renderer>(&self, state: &State, row: usize, renderer: &Renderer) -> f32
+    where
+        Renderer: text::Renderer<Font = Font>,
+    {
+        let row = &state.rows()[row];
+        let font = self.resolve_name_font(&row.entry, renderer);
+        let text_size = self.resolve_text_size(renderer);
+        let name = row.entry.name_width::<Renderer>(font, text_size);
+
+        if row.chain.is_empty() {
+            return name;
+        }
+
+        let separator = measure::<Renderer>(SEPARATOR, font, text_size);
+
+        row.chain
+            .iter()
+            .map(|segment| segment.name_width::<Renderer>(font, text_size) + separator)
+            .sum::<f32>()
+            + name
+    }
+
+    fn resolve_icon_font<Renderer>(&self, renderer: &Renderer) -> Font
+    where
+        Renderer: text::Renderer<Font = Font>,
+    {
+        self.config
+            .icon_font
+            .unwrap_or_else(|| renderer.default_font())
+    }
+
+    fn publish(&self, shell: &mut Shell<'_, Message>, event: FileTreeEvent) {
+        if let Some(on_event) = &self.config.on_event {
+            shell.publish(on_event(event));
+        }
+    }
+
+    fn publish_toggle(&self, shell: &mut Shell<'_, Message>, path: PathBuf, open: bool) {
+        if let Some(on_toggle) = &self.config.on_toggle {
+            shell.publish(on_toggle(path.clone(), open));
+        }
+
+        self.publish(
+            shell,
+            FileTreeEvent::Toggled {
+                path,
+                kind: EntryKind::Directory,
+                open,
+            },
+        );
+    }
+
+    /// Opens or closes the directory in the given row, unless the application decides that.
+    fn toggle(&self, state: &mut State, row: usize, shell: &mut Shell<'_, Message>) {
+        let open = !state.is_open(row);
+
+        for segment in state.rows()[row].segments() {
+            self.publish_toggle(shell, segment.path.clone(), open);
+        }
+
+        // in controlled mode, the application decides whether the directory is opened
+        if self.config.expanded.is_none() {
+            state.toggle(row);
+            shell.invalidate_layout();
+        }
+    }
+
+    fn update_hovered(
+        &self,
+        state: &mut State,
+        bounds: Rectangle,
+        cursor: Cursor,
+        shell: &mut Shell<'_, Message>,
+    ) {
+        let row = self.hovered_row(state, bounds, cursor);
+
+        if state.hovered() == row.map(|row| state.entry(row).path.as_path()) {
+            return;
+        }
+
+        // iced keeps the layout of the overlay until the layout is invalidated
+        if state.tooltip.is_some() {
+            shell.invalidate_layout();
+        }
+
+        if let Some(entry) = state.hover(row) {
+            self.publish(
+                shell,
+                FileTreeEvent::Unhovered {
+                    path: entry.path.clone(),
+                    kind: entry.kind(),
+                },
+            );
+        }
+
+        if let Some(row) = row {
+            let entry = state.entry(row);
+            self.publish(
+                shell,
+                FileTreeEvent::Hovered {
+                    path: entry.path.clone(),
+                    kind: entry.kind(),
+                },
+            );
+        }
+
+        if let Some(on_hover) = &self.config.on_hover {
+            shell.publish(on_hover(state.hovered().map(Path::to_path_buf)));
+        }
+
+        if let (Some(_), Some(hovered_at)) = (&self.config.tooltip, state.hovered_at()) {
+            shell.request_redraw(RedrawRequest::At(hovered_at + TOOLTIP_DELAY));
+        }
+    }
+
+    fn on_press<Renderer>(
+        &self,
+        state: &mut State,
+        bounds: Rectangle,
+        cursor: Cursor,
+        pointer: Pointer,
+        renderer: &Renderer,
+        shell: &mut Shell<'_, Message>,
+    ) -> Status
+    where
+        Renderer: text::Renderer<Font = Font> + svg::Renderer,
+    {
+        // a new press ends any drag whose release went elsewhere
+        state.drag = None;
+
+        let Some(row) = self.hovered_row(state, bounds, cursor) else {
+            if pointer.button() == mouse::Button::Left {
+                state.unfocus();
+            }
+
+            return Status::Ignored;
+        };
+
+        if state.tooltip.is_some() {
+            shell.invalidate_layout();
+        }
+
+        state.hide_tooltip();
+
+        let position = cursor.position().unwrap_or_default();
+        let button = pointer.button();
+        let count = match pointer {
+            Pointer::Mouse(button) => {
+                let click = Click::new(position, button, state.last_click);
+                state.last_click = Some(click);
+                click_count(click.kind())
+            }
+            Pointer::Finger { count } => count,
+        };
+
+        let entry = self.segment_at(state, bounds, row, position.x, renderer);
+        let (path, kind) = (entry.path.clone(), entry.kind());
+
+        // middle clicks usually open an entry somewhere else, without changing the selection
+        if matches!(button, mouse::Button::Left | mouse::Button::Right) && state.select(row, &path)
+        {
+            self.publish(
+                shell,
+                FileTreeEvent::SelectionChanged {
+                    path: path.clone(),
+                    kind,
+                },
+            );
+        }
+
+        self.publish(
+            shell,
+            FileTreeEvent::Clicked {
+                path: path.clone(),
+                kind,
+                button,
+                modifiers: state.modifiers,
+                count,
+            },
+        );
+
+        if button == mouse::Button::Right {
+            self.publish(
+                shell,
+                FileTreeEvent::ContextRequested {
+                    path,
+                    kind,
+                    position: Some(position),
+                },
+            );
+
+            return Status::Captured;
+        }
+
+        if button != mouse::Button::Left {
+            return Status::Captured;
+        }
+
+        // fingers drag entries with a long press instead, and never release the mouse button
+        if pointer == Pointer::Mouse(mouse::Button::Left) {
+            state.drag = Some(Drag {
+                origin: position,
+                entry: entry.clone(),
+                gesture: Gesture::Pending,
+            });
+        }
+
+        if entry.is_dir {
+            // empty directories don't draw a chevron
+            let chevron = self.row_bounds(state, bounds, row).x;
+            let on_chevron = !state.entry(row).is_empty()
+                && (chevron..chevron + self.config.row_height).contains(&position.x);
+
+            let toggle = on_chevron
+                || match self.config.toggle_on {
+                    ToggleOn::Click => true,
+                    ToggleOn::DoubleClick => count == 2,
+                    ToggleOn::Chevron => false,
+                };
+
+            if toggle {
+                self.toggle(state, row, shell);
+            }
+
+            return Status::Captured;
+        }
+
+        if let Some(on_single_click) = &self.config.on_single_click {
+            shell.publish(on_single_click(path.clone()));
+        }
+
+        if count == 2 {
+            if let Some(on_double_click) = &self.config.on_double_click {
+                shell.publish(on_double_click(path));
+            }
+        }
+
+        Status::Captured
+    }
+
+    /// Drags the entry the button was pressed on once the cursor moved far enough.
+    fn on_cursor_moved(
+        &self,
+        state: &mut State,
+        bounds: Rectangle,
+        cursor: Cursor,
+        position: Point,
+    ) -> Status {
+        let target = self
+            .dir_row_at(state, bounds, cursor)
+            .map(|row| state.rows()[row].entry.clone());
+
+        // the cursor follows a finger that was put down, so the button isn't held down anymore
+        if state.touch.is_some() {
+            state.drag = None;
+        }
+
+        let Some(drag) = state.drag.as_mut() else {
+            return Status::Ignored;
+        };
+
+        if matches!(drag.gesture, Gesture::Pending)
+            && position.distance(drag.origin) <= DRAG_DISTANCE
+        {
+            return Status::Ignored;
+        }
+
+        let target = target.filter(|target| drag.entry.moves_into(&target.path));
+        drag.gesture = Gesture::Dragging(target);
+
+        Status::Captured
+    }
+
+    fn on_release(&self, state: &mut State, shell: &mut Shell<'_, Message>) -> Status {
+        let Some(drag) = state.drag.take() else {
+            return Status::Ignored;
+        };
+
+        let Gesture::Dragging(target) = drag.gesture else {
+            return Status::Ignored;
+        };
+
+        let target = target.map(|target| target.path.clone());
+        self.on_drag_end(&drag.entry, target, shell);
+
+        Status::Captured
+    }
+
+    /// Returns the row of the directory an entry would be dragged onto at the cursor position.
+    fn dir_row_at(&self, state: &State, bounds: Rectangle, cursor: Cursor) -> Option<usize> {
+        let row = self.hovered_row(state, bounds, cursor)?;
+
+        if state.entry(row).is_dir {
+            Some(row)
+        } else {
+            state.rows()[row].parent
+        }
+    }
+
+    fn on_touch<Renderer>(
+        &self,
+        state: &mut State,
+        bounds: Rectangle,
+        cursor: Cursor,
+        event: touch::Event,
+        renderer: &Renderer,
+        shell: &mut Shell<'_, Message>,
+    ) -> Status
+    where
+        Renderer: text::Renderer<Font = Font> + svg::Renderer,
+    {
+        match event {
+            touch::Event::FingerPressed { id, position } => {
+                let row = self.hovered_row(state, bounds, cursor);
+                let pressed_at = Instant::now();
+                state.drag = None;
+
+                state.touch = row.map(|row| Touch {
+                    finger: id,
+                    origin: position,
+                    pressed_at,
+                    entry: state.rows()[row].entry.clone(),
+                    gesture: Gesture::Pending,
+                });
+
+                if state.touch.is_some() {
+                    shell.request_redraw(RedrawRequest::At(pressed_at + LONG_PRESS));
+                }
+
+                // the enclosing scrollable needs the touch in case it turns into scrolling
+                Status::Ignored
+            }
+            touch::Event::FingerMoved { id, position } => {
+                let target = self
+                    .dir_row_at(state, bounds, cursor)
+                    .map(|row| state.rows()[row].entry.clone());
+
+                let Some(touch) = state.touch.as_mut().filter(|touch| touch.finger == id) else {
+                    return Status::Ignored;
+                };
+
+                let moved = position.distance(touch.origin) > TOUCH_SLOP;
+                let target = target.filter(|target| touch.entry.moves_into(&target.path));
+
+                touch.gesture = match std::mem::replace(&mut touch.gesture, Gesture::Pending) {
+                    Gesture::Pending if moved => Gesture::Scrolling,
+                    Gesture::Held if moved => Gesture::Dragging(target),
+                    Gesture::Dragging(_) => Gesture::Dragging(target),
+                    gesture => gesture,
+                };
+
+                match touch.gesture {
+                    Gesture::Held | Gesture::Dragging(_) => Status::Captured,
+                    Gesture::Pending | Gesture::Scrolling => Status::Ignored,
+                }
+            }
+            touch::Event::FingerLifted { id, .. } => {
+                let Some(touch) = state.touch.take_if(|touch| touch.finger == id) else {
+                    return Status::Ignored;
+                };
+
+                match touch.gesture {
+                    Gesture::Pending => {
+                        self.on_tap(state, bounds, cursor, &touch.entry, renderer, shell)
+                    }
+                    Gesture::Held => Status::Captured,
+                    Gesture::Dragging(target) => {
+                        let target = target.map(|target| target.path.clone());
+                        self.on_drag_end(&touch.entry, target, shell);
+                        Status::Captured
+                    }
+                    Gesture::Scrolling => Status::Ignored,
+                }
+            }
+            touch::Event::FingerLost { id, .. } => {
+                state.touch.take_if(|touch| touch.finger == id);
+                Status::Ignored
+            }
+        }
+    }
+
+    /// Treats a tap like a left click, and a double tap as an activation of the entry.
+    fn on_tap<Renderer>(
+        &self,
+        state: &mut State,
+        bounds: Rectangle,
+        cursor: Cursor,
+        entry: &Entry,
+        renderer: &Renderer,
+        shell: &mut Shell<'_, Message>,
+    ) -> Status
+    where
+        Renderer: text::Renderer<Font = Font> + svg::Renderer,
+    {
+        // iced only counts clicks at the exact same position, which fingers hardly ever hit twice
+        let position = cursor.position().unwrap_or_default();
+        let now = Instant::now();
+        let double = state.last_tap.take().is_some_and(|tap| {
+            now - tap.at <= DOUBLE_TAP && position.distance(tap.position) <= TOUCH_SLOP
+        });
+
+        // a third tap starts over instead of making another double tap
+        if !double {
+            state.last_tap = Some(Tap { position, at: now });
+        }
+
+        let count = if double { 2 } else { 1 };
+        let status = self.on_press(
+            state,
+            bounds,
+            cursor,
+            Pointer::Finger { count },
+            renderer,
+            shell,
+        );
+
+        if double {
+            self.publish(
+                shell,
+                FileTreeEvent::Activated {
+                    path: entry.path.clone(),
+                    kind: entry.kind(),
+                },
+            );
+        }
+
+        status
+    }
+
+    fn on_drag_end(&self, entry: &Entry, target: Option<PathBuf>, shell: &mut Shell<'_, Message>) {
+        let Some(target) = target.filter(|target| entry.moves_into(target)) else {
+            return;
+        };
+
+        self.publish(
+            shell,
+            FileTreeEvent::Moved {
+                path: entry.path.clone(),
+                kind: entry.kind(),
+                target,
+            },
+        );
+    }
+
+    fn on_redraw(
+        &self,
+        state: &mut State,
+        now: Instant,
+        cursor: Cursor,
+        shell: &mut Shell<'_, Message>,
+    ) {
+        self.update_long_press(state, now, cursor, shell);
+
+        // the overlay of the previous tooltip would be drawn again otherwise
+        if self.update_tooltip(state, now) {
+            shell.invalidate_layout();
+        }
+    }
+
+    /// Turns a touch into a long press once the finger has been held down long enough.
+    fn update_long_press(
+        &self,
+        state: &mut State,
+        now: Instant,
+        cursor: Cursor,
+        shell: &mut Shell<'_, Message>,
+    ) {
+        let Some(touch) = state.touch.as_mut() else {
+            return;
+        };
+
+        if !matches!(touch.gesture, Gesture::Pending) || now < touch.pressed_at + LONG_PRESS {
+            return;
+        }
+
+        touch.gesture = Gesture::Held;
+
+        self.publish(
+            shell,
+            FileTreeEvent::ContextRequested {
+                path: touch.entry.path.clone(),
+                kind: touch.entry.kind(),
+                position: cursor.position(),
+            },
+        );
+    }
+
+    /// Prepares the tooltip of the hovered entry once it has been hovered long enough,
+    /// returning whether it was shown.
+    fn update_tooltip(&self, state: &mut State, now: Instant) -> bool {
+        let (Some(describe), Some(hovered_at)) = (&self.config.tooltip, state.hovered_at()) else {
+            return false;
+        };
+
+        if state.tooltip.is_some()
+            || state.touch.is_some()
+            || state.is_dragging()
+            || now < hovered_at + TOOLTIP_DELAY
+        {
+            return false;
+        }
+
+        let Some(path) = state.hovered() else {
+            return false;
+        };
+
+        state.tooltip = std::fs::metadata(path)
+            .ok()
+            .map(|metadata| describe(path, &metadata));
+
+        state.tooltip.is_some()
+    }
+
+    fn on_key_press(
+        &self,
+        state: &mut State,
+        key: &Key,
+        modifiers: Modifiers,
+        shell: &mut Shell<'_, Message>,
+    ) -> Status {
+        let Some(row) = state.focused_row() else {
+            return Status::Ignored;
+        };
+
+        let entry = state.rows()[row].entry.clone();
+        let (path, kind) = (entry.path.clone(), entry.kind());
+
+        match key {
+            Key::Named(key::Named::Enter) => {
+                self.publish(shell, FileTreeEvent::Activated { path, kind });
+
+                if entry.is_dir {
+                    self.toggle(state, row, shell);
+                }
+            }
+            Key::Named(key::Named::F2) => {
+                self.publish(shell, FileTreeEvent::RenameRequested { path, kind });
+            }
+            Key::Named(key::Named::ContextMenu) => {
+                self.publish(
+                    shell,
+                    FileTreeEvent::ContextRequested {
+                        path,
+                        kind,
+                        position: None,
+                    },
+                );
+            }
+            Key::Named(key::Named::F10) if modifiers.shift() => {
+                self.publish(
+                    shell,
+                    FileTreeEvent::ContextRequested {
+                        path,
+                        kind,
+                        position: None,
+                    },
+                );
+            }
+            _ => return Status::Ignored,
+        }
+
+        Status::Captured
+    }
+}
+
+impl<Message, Theme, Renderer> Widget<Message, Theme, Renderer> for FileTree<'_, Message, Theme>
+where
+    Message: Clone + 'static,
+    Theme: Catalog + 'static,
+    Renderer: text::Renderer<Font = Font> + svg::Renderer + 'static,
+{
     fn size(&self) -> Size<Length> {
-        self.0.size()
+        Size::new(self.config.width, Length::Shrink)
     }
 
     fn tag(&self) -> tree::Tag {
-        self.0.tag()
+        tree::Tag::of::<State>()
     }
 
     fn state(&self) -> tree::State {
-        self.0.state()
+        tree::State::new(State::default())
     }
 
     fn layout(&self, tree: &mut Tree, renderer: &Renderer, limits: &Limits) -> Node {
-        self.0.layout(tree, renderer, limits)
+        let state = tree.state.downcast_mut::<State>();
+        state.sync(&self.path, &self.config);
+
+        let height = state.rows().len() as f32 * self.config.row_height;
+
+        // measuring every name is only worth it when the tree is as wide as its contents
+        let intrinsic_width = if self.config.width == Length::Shrink {
+            self.intrinsic_width(state, renderer)
+        } else {
+            0.0
+        };
+
+        let width = limits
+            .resolve(
+                self.config.width,
+                Length::Shrink,
+                Size::new(intrinsic_width, height),
+            )
+            .width;
+
+        Node::new(Size::new(width, height))
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        _renderer: &Renderer,
+        operation: &mut dyn Operation,
+    ) {
+        let state = tree.state.downcast_mut::<State>();
+
+        operation.container(self.id.as_ref(), layout.bounds(), &mut |operation| {
+            operation.focusable(state, self.id.as_ref());
+            operation.custom(state, self.id.as_ref());
+        });
+
+        // the operation may have changed which directories are open, but the layout isn't updated
+        // after operations, so that's left to the next event
+        if state.sync(&self.path, &self.config) {
+            state.invalidate_layout();
+        }
     }
 
     fn on_event(
@@ -128,13 +1392,116 @@ where
         layout: Layout<'_>,
         cursor: Cursor,
         renderer: &Renderer,
-        clipboard: &mut dyn Clipboard,
+        _clipboard: &mut dyn Clipboard,
         shell: &mut Shell<'_, Message>,
-        viewport: &Rectangle,
+        _viewport: &Rectangle,
     ) -> Status {
-        self.0.on_event(
-            tree, event, layout, cursor, renderer, clipboard, shell, viewport,
-        )
+        let state = tree.state.downcast_mut::<State>();
+        let bounds = layout.bounds();
+
+        // changes made by operations are carried out and published as events come in
+        if state.take_relayout() || state.is_busy() {
+            state.expand_step();
+
+            for (path, open) in state.take_toggled() {
+                self.publish_toggle(shell, path, open);
+            }
+
+            if state.is_busy() {
+                shell.request_redraw(RedrawRequest::NextFrame);
+            }
+
+            shell.invalidate_layout();
+        }
+
+        match event {
+            Event::Mouse(event) => {
+                self.update_hovered(state, bounds, cursor, shell);
+
+                match event {
+                    mouse::Event::ButtonPressed(button) => {
+                        let pointer = Pointer::Mouse(button);
+                        self.on_press(state, bounds, cursor, pointer, renderer, shell)
+                    }
+                    mouse::Event::CursorMoved { position } => {
+                        self.on_cursor_moved(state, bounds, cursor, position)
+                    }
+                    mouse::Event::ButtonReleased(mouse::Button::Left) => {
+                        self.on_release(state, shell)
+                    }
+                    _ => Status::Ignored,
+                }
+            }
+            Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                state.modifiers = modifiers;
+                Status::Ignored
+            }
+            Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) => {
+                self.on_key_press(state, &key, modifiers, shell)
+            }
+            Event::Touch(event) => self.on_touch(state, bounds, cursor, event, renderer, shell),
+            Event::Window(window::Event::RedrawRequested(now)) => {
+                self.on_redraw(state, now, cursor, shell);
+                Status::Ignored
+            }
+            Event::Window(window::Event::FileDropped(dropped)) => {
+                let Some(row) = self.hovered_row(state, bounds, cursor) else {
+                    return Status::Ignored;
+                };
+
+                let entry = state.entry(row);
+                self.publish(
+                    shell,
+                    FileTreeEvent::Dropped {
+                        path: entry.path.clone(),
+                        kind: entry.kind(),
+                        dropped,
+                    },
+                );
+
+                Status::Captured
+            }
+            _ => Status::Ignored,
+        }
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        let state = tree.state.downcast_ref::<State>();
+        let content = state.tooltip.as_deref()?;
+        let row = state.row_of(state.hovered()?)?;
+
+        Some(overlay::Element::new(Box::new(Tooltip {
+            content,
+            anchor: self.row_bounds(state, layout.bounds(), row) + translation,
+            text_size: self.resolve_text_size(renderer),
+            font: self.config.font.unwrap_or_else(|| renderer.default_font()),
+            class: &self.config.tooltip_class,
+        })))
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        let state = tree.state.downcast_ref::<State>();
+
+        if state.is_dragging() {
+            mouse::Interaction::Grabbing
+        } else if self.hovered_row(state, layout.bounds(), cursor).is_some() {
+            mouse::Interaction::Pointer
+        } else {
+            mouse::Interaction::None
+        }
     }
 
     fn draw(
@@ -142,30 +1509,58 @@ where
         tree: &Tree,
         renderer: &mut Renderer,
         theme: &Theme,
-        style: &Style,
+        _style: &renderer::Style,
         layout: Layout<'_>,
         cursor: Cursor,
         viewport: &Rectangle,
     ) {
-        let Some(mut bounds) = layout.bounds().intersection(viewport) else {
+        let Some(mut clip) = layout.bounds().intersection(viewport) else {
             return;
         };
 
         // I have no clue why this is necessary
-        bounds.height += 1.0;
+        clip.height += 1.0;
+
+        let state = tree.state.downcast_ref::<State>();
+        let bounds = layout.bounds();
+        let rows = self.visible_rows(state, bounds, viewport);
+        let hovered = self.hovered_row(state, bounds, cursor);
+        let active = hovered
+            .or_else(|| state.selected_row())
+            .and_then(|row| state.rows()[row].parent);
+
+        state.set_first_visible(rows.start);
+
+        renderer.with_layer(clip, |renderer| {
+            for row in rows.clone() {
+                let status = if state.is_drop_target(row) {
+                    style::Status::DropTarget
+                } else if state.is_focused(row) {
+                    style::Status::Focused
+                } else if state.is_selected(row) {
+                    style::Status::Selected
+                } else if hovered == Some(row) {
+                    style::Status::Hovered
+                } else {
+                    style::Status::Idle
+                };
+
+                self.draw_row(state, row, renderer, theme, bounds, status);
+            }
 
-        renderer.with_layer(bounds, |renderer| {
-            self.0
-                .draw(tree, renderer, theme, style, layout, cursor, viewport);
+            self.draw_guides(state, rows, active, renderer, theme, bounds);
         });
     }
 }
 
-impl<Message> From<FileTree<Message>> for Element<'_, Message, Theme, Renderer>
+impl<'a, Message, Theme, Renderer> From<FileTree<'a, Message, Theme>>
+    for Element<'a, Message, Theme, Renderer>
 where
     Message: Clone + 'static,
+    Theme: Catalog + 'static,
+    Renderer: text::Renderer<Font = Font> + svg::Renderer + 'static,
 {
-    fn from(dir: FileTree<Message>) -> Self {
+    fn from(dir: FileTree<'a, Message, Theme>) -> Self {
         Self::new(dir)
     }
 }
@@ -0,0 +1,106 @@
+#[cfg(feature = "icons")]
+mod default;
+#[cfg(feature = "freedesktop")]
+mod freedesktop;
+#[cfg(feature = "icons")]
+mod glyph;
+
+#[cfg(feature = "icons")]
+pub use default::DefaultIcons;
+#[cfg(feature = "freedesktop")]
+pub use freedesktop::FreedesktopIcons;
+#[cfg(feature = "icons")]
+pub use glyph::{EmojiIcons, NerdFontIcons};
+use iced::{
+    advanced::{
+        svg::{self, Handle, Svg},
+        text::{self, LineHeight, Shaping, Wrapping},
+        Text,
+    },
+    alignment::{Horizontal, Vertical},
+    Color, Font, Pixels, Rectangle,
+};
+use std::{borrow::Cow, path::Path, rc::Rc};
+
+/// An icon drawn in front of the name of an entry in a [`FileTree`](crate::FileTree).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Icon {
+    /// An SVG image, tinted with the text color of the entry.
+    Svg(Handle),
+    /// An SVG image, drawn with its own colors.
+    ColoredSvg(Handle),
+    /// A text glyph, drawn with the [`icon_font`](crate::FileTree::icon_font) of the
+    /// [`FileTree`](crate::FileTree).
+    Glyph(Cow<'static, str>),
+}
+
+/// Decides which [`Icon`] is drawn for each entry in a [`FileTree`](crate::FileTree).
+pub trait IconProvider {
+    /// Returns the icon of the file at the given path, or `None` to draw the default file icon.
+    fn file_icon(&self, path: &Path) -> Option<Icon>;
+
+    /// Returns the icon drawn after the chevron of the directory at the given path, or `None` to
+    /// draw the default folder icon.
+    ///
+    /// Only used if [`FileTree::folder_icons`](crate::FileTree::folder_icons) is enabled.
+    fn dir_icon(&self, _path: &Path, _open: bool) -> Option<Icon> {
+        None
+    }
+}
+
+impl<T> IconProvider for &T
+where
+    T: IconProvider + ?Sized,
+{
+    fn file_icon(&self, path: &Path) -> Option<Icon> {
+        (**self).file_icon(path)
+    }
+
+    fn dir_icon(&self, path: &Path, open: bool) -> Option<Icon> {
+        (**self).dir_icon(path, open)
+    }
+}
+
+impl<T> IconProvider for Rc<T>
+where
+    T: IconProvider + ?Sized,
+{
+    fn file_icon(&self, path: &Path) -> Option<Icon> {
+        (**self).file_icon(path)
+    }
+
+    fn dir_icon(&self, path: &Path, open: bool) -> Option<Icon> {
+        (**self).dir_icon(path, open)
+    }
+}
+
+pub fn draw<Renderer>(
+    icon: Icon,
+    renderer: &mut Renderer,
+    bounds: Rectangle,
+    color: Color,
+    font: Font,
+    size: Pixels,
+) where
+    Renderer: text::Renderer<Font = Font> + svg::Renderer,
+{
+    match icon {
+        Icon::Svg(handle) => renderer.draw_svg(Svg::new(handle).color(color), bounds),
+        Icon::ColoredSvg(handle) => renderer.draw_svg(Svg::new(handle), bounds),
+        Icon::Glyph(glyph) => {
+            let glyph = Text {
+                content: glyph.into_owned(),
+                bounds: bounds.size(),
+                size,
+                line_height: LineHeight::default(),
+                font,
+                horizontal_alignment: Horizontal::Center,
+                vertical_alignment: Vertical::Center,
+                shaping: Shaping::Advanced,
+                wrapping: Wrapping::None,
+            };
+
+            renderer.fill_text(glyph, bounds.center(), color, bounds);
+        }
+    }
+}
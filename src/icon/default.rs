@@ -0,0 +1,63 @@
+use crate::{Icon, IconProvider};
+use iced::advanced::svg::Handle;
+use std::path::Path;
+
+const ARCHIVE: &[u8] = include_bytes!("../../assets/icons/document-archive.svg");
+const BRACES: &[u8] = include_bytes!("../../assets/icons/document-braces.svg");
+const CODE: &[u8] = include_bytes!("../../assets/icons/document-code.svg");
+const IMAGE: &[u8] = include_bytes!("../../assets/icons/document-image.svg");
+const LOCK: &[u8] = include_bytes!("../../assets/icons/document-lock.svg");
+const MARKDOWN: &[u8] = include_bytes!("../../assets/icons/document-markdown.svg");
+const SETTINGS: &[u8] = include_bytes!("../../assets/icons/document-settings.svg");
+const TERMINAL: &[u8] = include_bytes!("../../assets/icons/document-terminal.svg");
+
+const FOLDER: &[u8] = include_bytes!("../../assets/icons/folder.svg");
+const FOLDER_OPEN: &[u8] = include_bytes!("../../assets/icons/folder-open.svg");
+const FOLDER_CODE: &[u8] = include_bytes!("../../assets/icons/folder-code.svg");
+const FOLDER_GIT: &[u8] = include_bytes!("../../assets/icons/folder-git.svg");
+const FOLDER_IMAGE: &[u8] = include_bytes!("../../assets/icons/folder-image.svg");
+
+/// The built-in [`IconProvider`], covering common file types and a few special folders.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultIcons;
+
+impl IconProvider for DefaultIcons {
+    fn file_icon(&self, path: &Path) -> Option<Icon> {
+        let name = path.file_name()?.to_str()?;
+
+        let icon = match name {
+            ".editorconfig" | ".gitattributes" | ".gitignore" | ".gitmodules" | "Dockerfile"
+            | "Makefile" => SETTINGS,
+            _ => match &*path.extension()?.to_str()?.to_ascii_lowercase() {
+                "c" | "cc" | "cpp" | "cs" | "css" | "go" | "h" | "hpp" | "html" | "java" | "js"
+                | "jsx" | "kt" | "lua" | "mjs" | "py" | "rb" | "rs" | "scss" | "swift" | "ts"
+                | "tsx" | "wgsl" | "zig" => CODE,
+                "json" | "json5" | "jsonc" | "ron" => BRACES,
+                "cfg" | "conf" | "ini" | "toml" | "xml" | "yaml" | "yml" => SETTINGS,
+                "markdown" | "md" | "mdx" => MARKDOWN,
+                "avif" | "bmp" | "gif" | "ico" | "jpeg" | "jpg" | "png" | "svg" | "tiff"
+                | "webp" => IMAGE,
+                "7z" | "bz2" | "crate" | "gz" | "rar" | "tar" | "tgz" | "xz" | "zip" | "zst" => {
+                    ARCHIVE
+                }
+                "lock" => LOCK,
+                "bash" | "bat" | "cmd" | "fish" | "ps1" | "sh" | "zsh" => TERMINAL,
+                _ => return None,
+            },
+        };
+
+        Some(Icon::Svg(Handle::from_memory(icon)))
+    }
+
+    fn dir_icon(&self, path: &Path, open: bool) -> Option<Icon> {
+        let icon = match path.file_name()?.to_str()? {
+            ".git" => FOLDER_GIT,
+            "src" => FOLDER_CODE,
+            "assets" => FOLDER_IMAGE,
+            _ if open => FOLDER_OPEN,
+            _ => FOLDER,
+        };
+
+        Some(Icon::Svg(Handle::from_memory(icon)))
+    }
+}
@@ -0,0 +1,275 @@
+mod mime;
+mod theme;
+
+use crate::{Icon, IconProvider};
+use iced::advanced::svg::Handle;
+use mime::MimeDatabase;
+use std::{
+    cell::{OnceCell, RefCell},
+    collections::HashMap,
+    env,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+use theme::IconTheme;
+
+/// The standard icon theme size closest to the default row height.
+const SIZE: u32 = 22;
+
+/// An [`IconProvider`] that draws icons from the desktop's icon theme.
+///
+/// The MIME type of each file is resolved using the shared-mime-info database, and the matching
+/// icon is looked up following the freedesktop Icon Theme Specification. Only SVG icons are
+/// supported. Both the databases and the resolved icons are cached, and clones share the same
+/// cache, so the file system is only touched the first time an entry is drawn as long as the
+/// provider is kept around between views, for example in the application state.
+#[derive(Clone, Debug)]
+pub struct FreedesktopIcons {
+    theme: Option<String>,
+    size: u32,
+    symbolic: bool,
+    cache: Rc<Cache>,
+}
+
+#[derive(Debug, Default)]
+struct Cache {
+    data: OnceCell<Data>,
+    files: RefCell<HashMap<PathBuf, Option<Icon>>>,
+    icons: RefCell<HashMap<String, Option<Icon>>>,
+}
+
+#[derive(Debug)]
+struct Data {
+    mime: MimeDatabase,
+    theme: IconTheme,
+    special_dirs: HashMap<PathBuf, &'static str>,
+}
+
+impl Default for FreedesktopIcons {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FreedesktopIcons {
+    /// Creates a new [`FreedesktopIcons`] using the icon theme configured for GTK or KDE, or
+    /// `hicolor` if there is none.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            theme: None,
+            size: SIZE,
+            symbolic: false,
+            cache: Rc::default(),
+        }
+    }
+
+    /// Sets the name of the icon theme to use instead of the configured one.
+    #[must_use]
+    pub fn theme(mut self, theme: impl Into<String>) -> Self {
+        self.theme = Some(theme.into());
+        self.cache = Rc::default();
+        self
+    }
+
+    /// Sets the icon size in pixels that is preferred when the theme provides multiple sizes (22
+    /// pixels by default).
+    #[must_use]
+    pub fn size(mut self, size: u32) -> Self {
+        self.size = size;
+        self.cache = Rc::default();
+        self
+    }
+
+    /// Enables or disables preferring symbolic icons, which are tinted with the text color
+    /// (disabled by default).
+    #[must_use]
+    pub fn symbolic(mut self, symbolic: bool) -> Self {
+        self.symbolic = symbolic;
+        self.cache = Rc::default();
+        self
+    }
+
+    fn data(&self) -> &Data {
+        self.cache.data.get_or_init(|| {
+            let data_dirs = data_dirs();
+            let theme = self.theme.clone().or_else(configured_theme);
+
+            Data {
+                mime: MimeDatabase::load(&data_dirs),
+                theme: IconTheme::load(theme.as_deref().unwrap_or("hicolor"), &data_dirs),
+                special_dirs: special_dirs(),
+            }
+        })
+    }
+
+    fn icon(&self, name: &str) -> Option<Icon> {
+        if let Some(icon) = self.cache.icons.borrow().get(name) {
+            return icon.clone();
+        }
+
+        let theme = &self.data().theme;
+        let icon = self
+            .symbolic
+            .then(|| theme.lookup(&format!("{name}-symbolic"), self.size))
+            .flatten()
+            .map(|path| Icon::Svg(Handle::from_path(path)))
+            .or_else(|| {
+                theme
+                    .lookup(name, self.size)
+                    .map(|path| Icon::ColoredSvg(Handle::from_path(path)))
+            });
+
+        self.cache
+            .icons
+            .borrow_mut()
+            .insert(name.to_owned(), icon.clone());
+
+        icon
+    }
+}
+
+impl IconProvider for FreedesktopIcons {
+    fn file_icon(&self, path: &Path) -> Option<Icon> {
+        if let Some(icon) = self.cache.files.borrow().get(path) {
+            return icon.clone();
+        }
+
+        let mime = &self.data().mime;
+        let icon = mime
+            .icon_names(&mime.mime_type(path))
+            .iter()
+            .find_map(|name| self.icon(name));
+
+        self.cache
+            .files
+            .borrow_mut()
+            .insert(path.to_owned(), icon.clone());
+
+        icon
+    }
+
+    fn dir_icon(&self, path: &Path, open: bool) -> Option<Icon> {
+        self.data()
+            .special_dirs
+            .get(path)
+            .copied()
+            .into_iter()
+            .chain(open.then_some("folder-open"))
+            .chain(["folder"])
+            .find_map(|name| self.icon(name))
+    }
+}
+
+/// Parses an ini-style file into its sections, ignoring comments and malformed lines.
+fn parse_ini(contents: &str) -> HashMap<&str, HashMap<&str, &str>> {
+    let mut sections = HashMap::<_, HashMap<_, _>>::new();
+    let mut section = "";
+
+    for line in contents.lines().map(str::trim) {
+        if line.starts_with(['#', ';']) {
+            continue;
+        }
+
+        if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            section = name;
+        } else if let Some((key, value)) = line.split_once('=') {
+            sections
+                .entry(section)
+                .or_default()
+                .insert(key.trim(), value.trim());
+        }
+    }
+
+    sections
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .filter(|home| !home.is_empty())
+        .map(PathBuf::from)
+}
+
+fn config_home() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|home| home.join(".config")))
+}
+
+/// Returns the XDG data dirs, ordered from most to least important.
+fn data_dirs() -> Vec<PathBuf> {
+    let data_home = env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|home| home.join(".local/share")));
+
+    let data_dirs = env::var_os("XDG_DATA_DIRS")
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".into());
+
+    data_home
+        .into_iter()
+        .chain(env::split_paths(&data_dirs))
+        .collect()
+}
+
+/// Reads the name of the icon theme configured for GTK or KDE.
+fn configured_theme() -> Option<String> {
+    let config_home = config_home()?;
+
+    [
+        ("gtk-4.0/settings.ini", "Settings", "gtk-icon-theme-name"),
+        ("gtk-3.0/settings.ini", "Settings", "gtk-icon-theme-name"),
+        ("kdeglobals", "Icons", "Theme"),
+    ]
+    .into_iter()
+    .find_map(|(file, section, key)| {
+        let contents = std::fs::read_to_string(config_home.join(file)).ok()?;
+        let theme = *parse_ini(&contents).get(section)?.get(key)?;
+
+        Some(theme.trim_matches('"').to_owned())
+    })
+}
+
+/// Maps the home directory and the XDG user directories to their icon names.
+fn special_dirs() -> HashMap<PathBuf, &'static str> {
+    let Some(home) = home_dir() else {
+        return HashMap::new();
+    };
+
+    let user_dirs = config_home()
+        .and_then(|config| std::fs::read_to_string(config.join("user-dirs.dirs")).ok())
+        .unwrap_or_default();
+
+    user_dirs
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            let icon = match key.trim() {
+                "XDG_DESKTOP_DIR" => "user-desktop",
+                "XDG_DOCUMENTS_DIR" => "folder-documents",
+                "XDG_DOWNLOAD_DIR" => "folder-download",
+                "XDG_MUSIC_DIR" => "folder-music",
+                "XDG_PICTURES_DIR" => "folder-pictures",
+                "XDG_PUBLICSHARE_DIR" => "folder-publicshare",
+                "XDG_TEMPLATES_DIR" => "folder-templates",
+                "XDG_VIDEOS_DIR" => "folder-videos",
+                _ => return None,
+            };
+
+            let value = value.trim().trim_matches('"');
+            let path = value.strip_prefix("$HOME").map_or_else(
+                || PathBuf::from(value),
+                |rest| home.join(rest.trim_start_matches('/')),
+            );
+
+            // a user dir pointing at the home directory means it's disabled
+            (path != home).then_some((path, icon))
+        })
+        .chain([(home.clone(), "user-home")])
+        .collect()
+}
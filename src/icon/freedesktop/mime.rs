@@ -0,0 +1,580 @@
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    fs::File,
+    io::Read as _,
+    path::{Path, PathBuf},
+};
+
+const MAGIC_HEADER: &[u8] = b"MIME-Magic\0\n";
+const MAX_EXTENT: usize = 16 * 1024;
+
+/// The shared-mime-info database, merged from every `mime` directory in the XDG data dirs.
+#[derive(Debug, Default)]
+pub struct MimeDatabase {
+    globs: Vec<Glob>,
+    magic: Vec<Magic>,
+    aliases: HashMap<String, String>,
+    icons: HashMap<String, String>,
+    generic_icons: HashMap<String, String>,
+    extent: usize,
+}
+
+#[derive(Debug)]
+struct Glob {
+    weight: u32,
+    mime: String,
+    pattern: String,
+    case_sensitive: bool,
+}
+
+#[derive(Debug)]
+struct Magic {
+    priority: u32,
+    mime: String,
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug)]
+struct Rule {
+    indent: u32,
+    offset: usize,
+    value: Vec<u8>,
+    mask: Option<Vec<u8>>,
+    range: usize,
+}
+
+impl MimeDatabase {
+    /// Loads the database from the given data dirs, ordered from most to least important.
+    pub fn load(data_dirs: &[PathBuf]) -> Self {
+        let mut db = Self::default();
+
+        for dir in data_dirs.iter().map(|dir| dir.join("mime")) {
+            if let Ok(globs) = std::fs::read_to_string(dir.join("globs2")) {
+                db.parse_globs2(&globs);
+            } else if let Ok(globs) = std::fs::read_to_string(dir.join("globs")) {
+                db.parse_globs(&globs);
+            }
+
+            if let Ok(magic) = std::fs::read(dir.join("magic")) {
+                db.parse_magic(&magic);
+            }
+
+            if let Ok(aliases) = std::fs::read_to_string(dir.join("aliases")) {
+                parse_map(&aliases, ' ', &mut db.aliases);
+            }
+
+            if let Ok(icons) = std::fs::read_to_string(dir.join("icons")) {
+                parse_map(&icons, ':', &mut db.icons);
+            }
+
+            if let Ok(icons) = std::fs::read_to_string(dir.join("generic-icons")) {
+                parse_map(&icons, ':', &mut db.generic_icons);
+            }
+        }
+
+        db.globs.sort_by_key(|glob| Reverse(glob.weight));
+        db.magic.sort_by_key(|magic| Reverse(magic.priority));
+        db.extent = db
+            .magic
+            .iter()
+            .flat_map(|magic| &magic.rules)
+            .map(|rule| rule.offset + rule.range + rule.value.len())
+            .max()
+            .unwrap_or_default()
+            .min(MAX_EXTENT);
+
+        db
+    }
+
+    fn parse_globs2(&mut self, globs: &str) {
+        for line in globs.lines().filter(|line| !line.starts_with('#')) {
+            let mut fields = line.split(':');
+
+            let (Some(Ok(weight)), Some(mime), Some(pattern)) =
+                (fields.next().map(str::parse), fields.next(), fields.next())
+            else {
+                continue;
+            };
+
+            let case_sensitive = fields
+                .next()
+                .is_some_and(|flags| flags.split(',').any(|flag| flag == "cs"));
+
+            self.push_glob(weight, mime, pattern, case_sensitive);
+        }
+    }
+
+    fn parse_globs(&mut self, globs: &str) {
+        for line in globs.lines().filter(|line| !line.starts_with('#')) {
+            if let Some((mime, pattern)) = line.split_once(':') {
+                self.push_glob(50, mime, pattern, false);
+            }
+        }
+    }
+
+    fn push_glob(&mut self, weight: u32, mime: &str, pattern: &str, case_sensitive: bool) {
+        self.globs.push(Glob {
+            weight,
+            mime: mime.to_owned(),
+            pattern: if case_sensitive {
+                pattern.to_owned()
+            } else {
+                pattern.to_lowercase()
+            },
+            case_sensitive,
+        });
+    }
+
+    fn parse_magic(&mut self, magic: &[u8]) {
+        let Some(mut magic) = magic.strip_prefix(MAGIC_HEADER) else {
+            return;
+        };
+
+        while let Some(rest) = magic.strip_prefix(b"[") {
+            let Some((priority, mime, rest)) = parse_section_header(rest) else {
+                return;
+            };
+
+            let mut rules = Vec::new();
+            magic = rest;
+
+            while !magic.is_empty() && !magic.starts_with(b"[") {
+                let Some((rule, rest)) = parse_rule(magic) else {
+                    return;
+                };
+
+                rules.push(rule);
+                magic = rest;
+            }
+
+            self.magic.push(Magic {
+                priority,
+                mime,
+                rules,
+            });
+        }
+    }
+
+    /// Determines the MIME type of the file at the given path, first by its name and then by its
+    /// contents.
+    pub fn mime_type(&self, path: &Path) -> String {
+        if let Some(mime) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| self.match_globs(name))
+        {
+            return mime.to_owned();
+        }
+
+        let data = File::open(path)
+            .and_then(|file| {
+                let mut data = Vec::new();
+                file.take(self.extent.max(512) as u64)
+                    .read_to_end(&mut data)?;
+                Ok(data)
+            })
+            .unwrap_or_default();
+
+        if let Some(magic) = self.magic.iter().find(|magic| magic.matches(&data)) {
+            return magic.mime.clone();
+        }
+
+        if data.contains(&0) {
+            "application/octet-stream".to_owned()
+        } else {
+            "text/plain".to_owned()
+        }
+    }
+
+    fn match_globs(&self, name: &str) -> Option<&str> {
+        let lowercase = name.to_lowercase();
+
+        self.globs
+            .iter()
+            .filter(|glob| {
+                glob_matches(
+                    &glob.pattern,
+                    if glob.case_sensitive {
+                        name
+                    } else {
+                        &lowercase
+                    },
+                )
+            })
+            .min_by_key(|glob| (Reverse(glob.weight), Reverse(glob.pattern.len())))
+            .map(|glob| &*glob.mime)
+    }
+
+    /// Returns the names of the icons that may represent the given MIME type, from most to least
+    /// specific.
+    pub fn icon_names(&self, mime: &str) -> Vec<String> {
+        let mime = self.aliases.get(mime).map_or(mime, String::as_str);
+
+        let mut names = Vec::with_capacity(3);
+        names.extend(self.icons.get(mime).cloned());
+        names.push(mime.replace('/', "-"));
+        names.push(self.generic_icons.get(mime).cloned().unwrap_or_else(|| {
+            let media = mime.split_once('/').map_or(mime, |(media, _)| media);
+            format!("{media}-x-generic")
+        }));
+
+        names
+    }
+}
+
+impl Magic {
+    fn matches(&self, data: &[u8]) -> bool {
+        any_rule_matches(&self.rules, data)
+    }
+}
+
+/// Checks whether any top-level rule in `rules` matches, where a rule with nested rules only
+/// matches if one of its nested rules matches as well.
+fn any_rule_matches(rules: &[Rule], data: &[u8]) -> bool {
+    let mut i = 0;
+
+    while let Some(rule) = rules.get(i) {
+        let end = rules[i + 1..]
+            .iter()
+            .position(|nested| nested.indent <= rule.indent)
+            .map_or(rules.len(), |end| i + 1 + end);
+        let nested = &rules[i + 1..end];
+
+        if rule.matches(data) && (nested.is_empty() || any_rule_matches(nested, data)) {
+            return true;
+        }
+
+        i = end;
+    }
+
+    false
+}
+
+impl Rule {
+    fn matches(&self, data: &[u8]) -> bool {
+        (self.offset..self.offset + self.range).any(|start| {
+            data.get(start..start + self.value.len())
+                .is_some_and(|window| {
+                    window
+                        .iter()
+                        .zip(&self.value)
+                        .enumerate()
+                        .all(|(i, (data, value))| {
+                            let mask = self.mask.as_ref().map_or(u8::MAX, |mask| mask[i]);
+                            data & mask == value & mask
+                        })
+                })
+        })
+    }
+}
+
+fn parse_map(contents: &str, separator: char, map: &mut HashMap<String, String>) {
+    for line in contents.lines().filter(|line| !line.starts_with('#')) {
+        if let Some((key, value)) = line.split_once(separator) {
+            map.entry(key.to_owned())
+                .or_insert_with(|| value.to_owned());
+        }
+    }
+}
+
+fn parse_section_header(magic: &[u8]) -> Option<(u32, String, &[u8])> {
+    let end = magic.iter().position(|&b| b == b'\n')?;
+    let header = std::str::from_utf8(magic[..end].strip_suffix(b"]")?).ok()?;
+    let (priority, mime) = header.split_once(':')?;
+
+    Some((priority.parse().ok()?, mime.to_owned(), &magic[end + 1..]))
+}
+
+fn parse_rule(magic: &[u8]) -> Option<(Rule, &[u8])> {
+    let (indent, magic) = parse_number(magic);
+    let magic = magic.strip_prefix(b">")?;
+    let (offset, magic) = parse_number(magic);
+    let magic = magic.strip_prefix(b"=")?;
+
+    let (len, magic) = magic.split_first_chunk::<2>()?;
+    let len = usize::from(u16::from_be_bytes(*len));
+
+    let mut value = magic.get(..len)?.to_vec();
+    let mut magic = &magic[len..];
+
+    let mut mask = None;
+    if let Some(rest) = magic.strip_prefix(b"&") {
+        mask = Some(rest.get(..len)?.to_vec());
+        magic = &rest[len..];
+    }
+
+    let mut word_size = 1;
+    if let Some(rest) = magic.strip_prefix(b"~") {
+        (word_size, magic) = parse_number(rest);
+    }
+
+    let mut range = 1;
+    if let Some(rest) = magic.strip_prefix(b"+") {
+        (range, magic) = parse_number(rest);
+    }
+
+    // unknown extensions may follow, but the rule always ends at the next newline
+    let end = magic.iter().position(|&b| b == b'\n')?;
+    magic = &magic[end + 1..];
+
+    if cfg!(target_endian = "little") && word_size > 1 {
+        value.chunks_mut(word_size).for_each(<[u8]>::reverse);
+        mask.iter_mut()
+            .flat_map(|mask| mask.chunks_mut(word_size))
+            .for_each(<[u8]>::reverse);
+    }
+
+    let range = range.max(1);
+
+    // a malformed file could otherwise make matching overflow
+    offset.checked_add(range)?.checked_add(len)?;
+
+    let rule = Rule {
+        indent: indent.try_into().ok()?,
+        offset,
+        value,
+        mask,
+        range,
+    };
+
+    Some((rule, magic))
+}
+
+fn parse_number(magic: &[u8]) -> (usize, &[u8]) {
+    let len = magic.iter().take_while(|b| b.is_ascii_digit()).count();
+    let number = magic[..len].iter().fold(0, |n: usize, &b| {
+        n.saturating_mul(10).saturating_add(usize::from(b - b'0'))
+    });
+
+    (number, &magic[len..])
+}
+
+/// Matches a file name against a shell-style glob pattern supporting `*`, `?` and `[...]`.
+fn glob_matches(pattern: &str, name: &str) -> bool {
+    if let Some(suffix) = pattern.strip_prefix('*') {
+        if !suffix.contains(['*', '?', '[']) {
+            return name.ends_with(suffix);
+        }
+    } else if !pattern.contains(['*', '?', '[']) {
+        return pattern == name;
+    }
+
+    let pattern = pattern.chars().collect::<Box<[_]>>();
+    let name = name.chars().collect::<Box<[_]>>();
+
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack = None;
+
+    while n < name.len() {
+        if pattern.get(p) == Some(&'*') {
+            backtrack = Some((p, n));
+            p += 1;
+            continue;
+        }
+
+        if let Some(len) = match_char(&pattern[p..], name[n]) {
+            p += len;
+            n += 1;
+            continue;
+        }
+
+        let Some((star, matched)) = backtrack else {
+            return false;
+        };
+
+        p = star + 1;
+        n = matched + 1;
+        backtrack = Some((star, n));
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Matches a single character against the start of the pattern, returning the length of the
+/// pattern element that matched.
+fn match_char(pattern: &[char], c: char) -> Option<usize> {
+    match *pattern.first()? {
+        '?' => Some(1),
+        '[' => {
+            let negated = matches!(pattern.get(1), Some('!' | '^'));
+            let start = if negated { 2 } else { 1 };
+            let Some(end) = pattern
+                .iter()
+                .skip(start + 1)
+                .position(|&c| c == ']')
+                .map(|end| start + 1 + end)
+            else {
+                return (c == '[').then_some(1);
+            };
+
+            let class = &pattern[start..end];
+            let mut matched = false;
+            let mut i = 0;
+
+            while i < class.len() {
+                if class.get(i + 1) == Some(&'-') && i + 2 < class.len() {
+                    matched |= (class[i]..=class[i + 2]).contains(&c);
+                    i += 3;
+                } else {
+                    matched |= class[i] == c;
+                    i += 1;
+                }
+            }
+
+            (matched != negated).then_some(end + 1)
+        }
+        literal => (literal == c).then_some(1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_rules(mut magic: &[u8]) -> Vec<Rule> {
+        let mut rules = Vec::new();
+
+        while !magic.is_empty() {
+            let (rule, rest) = parse_rule(magic).unwrap();
+            rules.push(rule);
+            magic = rest;
+        }
+
+        rules
+    }
+
+    #[test]
+    fn glob_literals_and_suffixes() {
+        assert!(glob_matches("Makefile", "Makefile"));
+        assert!(!glob_matches("Makefile", "makefile"));
+        assert!(glob_matches("*.txt", "notes.txt"));
+        assert!(glob_matches("*.txt", ".txt"));
+        assert!(!glob_matches("*.txt", "notes.txt.bak"));
+    }
+
+    #[test]
+    fn glob_wildcards() {
+        assert!(glob_matches("?.md", "a.md"));
+        assert!(!glob_matches("?.md", "ab.md"));
+        assert!(glob_matches("a*b*c", "axxbyyc"));
+        assert!(glob_matches("a*b*c", "abbc"));
+        assert!(!glob_matches("a*b*c", "axxbyy"));
+        assert!(glob_matches("*.tar.*", "archive.tar.gz"));
+        assert!(glob_matches("README*", "README"));
+    }
+
+    #[test]
+    fn glob_classes() {
+        assert!(glob_matches("*.[ch]", "main.c"));
+        assert!(glob_matches("*.[ch]", "main.h"));
+        assert!(!glob_matches("*.[ch]", "main.o"));
+        assert!(glob_matches("[a-c]x", "bx"));
+        assert!(!glob_matches("[a-c]x", "dx"));
+        assert!(glob_matches("[!a]bc", "xbc"));
+        assert!(!glob_matches("[!a]bc", "abc"));
+        assert!(glob_matches("[]]", "]"));
+        assert!(glob_matches("[abc", "[abc"));
+    }
+
+    #[test]
+    fn rule_with_defaults() {
+        let (rule, rest) = parse_rule(b"0>4=\x00\x02PK\nrest").unwrap();
+
+        assert_eq!(rule.indent, 0);
+        assert_eq!(rule.offset, 4);
+        assert_eq!(rule.value, b"PK");
+        assert_eq!(rule.mask, None);
+        assert_eq!(rule.range, 1);
+        assert_eq!(rest, b"rest");
+    }
+
+    #[test]
+    fn rule_with_mask_word_size_and_range() {
+        let (rule, rest) = parse_rule(b"1>0=\x00\x02\x12\x34&\xff\x00~2+8\n").unwrap();
+
+        assert_eq!(rule.indent, 1);
+        assert_eq!(rule.value, 0x1234u16.to_ne_bytes());
+        assert_eq!(rule.mask.unwrap(), 0xff00u16.to_ne_bytes());
+        assert_eq!(rule.range, 8);
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn rule_without_indent_or_with_extensions() {
+        let (rule, _) = parse_rule(b">0=\x00\x01A!unknown\n").unwrap();
+
+        assert_eq!(rule.indent, 0);
+        assert_eq!(rule.value, b"A");
+    }
+
+    #[test]
+    fn malformed_rules() {
+        // truncated value
+        assert!(parse_rule(b">0=\x00\x04AB\n").is_none());
+        // truncated mask
+        assert!(parse_rule(b">0=\x00\x02AB&\xff\n").is_none());
+        // missing newline
+        assert!(parse_rule(b">0=\x00\x02AB").is_none());
+        // an offset that overflows once the range is added
+        assert!(parse_rule(b">99999999999999999999999=\x00\x01A+8\n").is_none());
+    }
+
+    #[test]
+    fn rule_matches_within_range() {
+        let rules = parse_rules(b">2=\x00\x02PK+3\n");
+
+        assert!(any_rule_matches(&rules, b"..PK"));
+        assert!(any_rule_matches(&rules, b"....PK"));
+        assert!(!any_rule_matches(&rules, b".....PK"));
+        assert!(!any_rule_matches(&rules, b"..P"));
+    }
+
+    #[test]
+    fn rule_matches_with_mask() {
+        let rules = parse_rules(b">0=\x00\x01\x40&\xf0\n");
+
+        assert!(any_rule_matches(&rules, &[0x4f]));
+        assert!(!any_rule_matches(&rules, &[0x5f]));
+    }
+
+    #[test]
+    fn nested_rules() {
+        let rules =
+            parse_rules(b">0=\x00\x02PK\n1>4=\x00\x03odt\n1>4=\x00\x03ods\n>0=\x00\x03GIF\n");
+
+        assert!(any_rule_matches(&rules, b"PK..odt"));
+        assert!(any_rule_matches(&rules, b"PK..ods"));
+        assert!(!any_rule_matches(&rules, b"PK..zip"));
+        assert!(any_rule_matches(&rules, b"GIF89a"));
+        assert!(!any_rule_matches(&rules, b""));
+    }
+
+    #[test]
+    fn magic_sections() {
+        let mut db = MimeDatabase::default();
+        db.parse_magic(
+            b"MIME-Magic\0\n[50:application/zip]\n>0=\x00\x02PK\n[80:image/gif]\n>0=\x00\x03GIF\n",
+        );
+
+        assert_eq!(db.magic.len(), 2);
+        assert_eq!(db.magic[0].priority, 50);
+        assert_eq!(db.magic[0].mime, "application/zip");
+        assert!(db.magic[0].matches(b"PK\x03\x04"));
+        assert_eq!(db.magic[1].mime, "image/gif");
+    }
+
+    #[test]
+    fn glob_weights() {
+        let mut db = MimeDatabase::default();
+        db.parse_globs2(
+            "# comment\n50:text/plain:*.txt\n60:text/x-readme:README*\n50:text/x-c:*.C:cs\n",
+        );
+        db.globs.sort_by_key(|glob| Reverse(glob.weight));
+
+        assert_eq!(db.match_globs("notes.TXT"), Some("text/plain"));
+        assert_eq!(db.match_globs("README.txt"), Some("text/x-readme"));
+        assert_eq!(db.match_globs("main.C"), Some("text/x-c"));
+        assert_eq!(db.match_globs("main.c"), None);
+    }
+}
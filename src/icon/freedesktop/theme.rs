@@ -0,0 +1,188 @@
+use super::{home_dir, parse_ini};
+use std::path::PathBuf;
+
+/// An icon theme following the freedesktop Icon Theme Specification, together with the themes it
+/// inherits from.
+#[derive(Debug, Default)]
+pub struct IconTheme {
+    themes: Vec<Theme>,
+    pixmaps: Vec<PathBuf>,
+}
+
+#[derive(Debug)]
+struct Theme {
+    roots: Vec<PathBuf>,
+    dirs: Vec<ThemeDir>,
+}
+
+#[derive(Debug)]
+struct ThemeDir {
+    path: String,
+    size: u32,
+    min_size: u32,
+    max_size: u32,
+    threshold: u32,
+    kind: DirKind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DirKind {
+    Fixed,
+    Scalable,
+    Threshold,
+}
+
+impl IconTheme {
+    /// Loads the theme with the given name and every theme it inherits from, falling back to
+    /// `hicolor` last.
+    pub fn load(name: &str, data_dirs: &[PathBuf]) -> Self {
+        let base_dirs = home_dir()
+            .map(|home| home.join(".icons"))
+            .into_iter()
+            .chain(data_dirs.iter().map(|dir| dir.join("icons")))
+            .collect::<Box<_>>();
+
+        let mut names = vec![name.to_owned()];
+        let mut themes = Vec::new();
+        let mut i = 0;
+
+        while i < names.len() {
+            if let Some((theme, parents)) = Theme::load(&names[i], &base_dirs) {
+                themes.push(theme);
+
+                for parent in parents {
+                    if !names.contains(&parent) {
+                        names.push(parent);
+                    }
+                }
+            }
+
+            i += 1;
+
+            if i == names.len() && !names.iter().any(|name| name == "hicolor") {
+                names.push("hicolor".to_owned());
+            }
+        }
+
+        Self {
+            themes,
+            pixmaps: vec![PathBuf::from("/usr/share/pixmaps")],
+        }
+    }
+
+    /// Looks up the SVG file of the icon with the given name, preferring the given size.
+    pub fn lookup(&self, name: &str, size: u32) -> Option<PathBuf> {
+        let file_name = format!("{name}.svg");
+
+        self.themes
+            .iter()
+            .find_map(|theme| theme.lookup(&file_name, size))
+            .or_else(|| {
+                self.pixmaps
+                    .iter()
+                    .map(|dir| dir.join(&file_name))
+                    .find(|path| path.is_file())
+            })
+    }
+}
+
+impl Theme {
+    /// Loads a single theme, returning it along with the names of the themes it inherits from.
+    fn load(name: &str, base_dirs: &[PathBuf]) -> Option<(Self, Vec<String>)> {
+        let roots = base_dirs
+            .iter()
+            .map(|dir| dir.join(name))
+            .filter(|dir| dir.is_dir())
+            .collect::<Vec<_>>();
+
+        let index = roots
+            .iter()
+            .find_map(|root| std::fs::read_to_string(root.join("index.theme")).ok())?;
+        let index = parse_ini(&index);
+        let theme = index.get("Icon Theme")?;
+
+        let dirs = theme
+            .get("Directories")
+            .into_iter()
+            .flat_map(|dirs| dirs.split(','))
+            .filter(|dir| !dir.is_empty())
+            .filter_map(|dir| {
+                let section = index.get(dir)?;
+                let get = |key| section.get(key).and_then(|value| value.parse().ok());
+
+                if get("Scale").unwrap_or(1) != 1 {
+                    return None;
+                }
+
+                let size = get("Size")?;
+
+                Some(ThemeDir {
+                    path: dir.to_owned(),
+                    size,
+                    min_size: get("MinSize").unwrap_or(size),
+                    max_size: get("MaxSize").unwrap_or(size),
+                    threshold: get("Threshold").unwrap_or(2),
+                    kind: match section.get("Type").copied() {
+                        Some("Fixed") => DirKind::Fixed,
+                        Some("Scalable") => DirKind::Scalable,
+                        _ => DirKind::Threshold,
+                    },
+                })
+            })
+            .collect();
+
+        let parents = theme
+            .get("Inherits")
+            .into_iter()
+            .flat_map(|parents| parents.split(','))
+            .filter(|parent| !parent.is_empty())
+            .map(str::to_owned)
+            .collect();
+
+        Some((Self { roots, dirs }, parents))
+    }
+
+    fn lookup(&self, file_name: &str, size: u32) -> Option<PathBuf> {
+        let candidates = self.dirs.iter().flat_map(|dir| {
+            self.roots
+                .iter()
+                .map(move |root| (dir, root.join(&dir.path).join(file_name)))
+        });
+
+        candidates
+            .clone()
+            .filter(|(dir, _)| dir.matches_size(size))
+            .find(|(_, path)| path.is_file())
+            .or_else(|| {
+                candidates
+                    .filter(|(_, path)| path.is_file())
+                    .min_by_key(|(dir, _)| dir.size_distance(size))
+            })
+            .map(|(_, path)| path)
+    }
+}
+
+impl ThemeDir {
+    fn matches_size(&self, size: u32) -> bool {
+        match self.kind {
+            DirKind::Fixed => self.size == size,
+            DirKind::Scalable => (self.min_size..=self.max_size).contains(&size),
+            DirKind::Threshold => self.size.abs_diff(size) <= self.threshold,
+        }
+    }
+
+    fn size_distance(&self, size: u32) -> u32 {
+        match self.kind {
+            DirKind::Fixed => self.size.abs_diff(size),
+            DirKind::Scalable => self
+                .min_size
+                .saturating_sub(size)
+                .max(size.saturating_sub(self.max_size)),
+            DirKind::Threshold => self
+                .size
+                .saturating_sub(self.threshold)
+                .saturating_sub(size)
+                .max(size.saturating_sub(self.size + self.threshold)),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{Icon, IconProvider};
+use std::{borrow::Cow, path::Path};
+
+/// An [`IconProvider`] that draws [Nerd Font](https://www.nerdfonts.com/) glyphs.
+///
+/// The glyphs are only drawn correctly if a Nerd Font is loaded and set as the
+/// [`icon_font`](crate::FileTree::icon_font) of the [`FileTree`](crate::FileTree). Use
+/// [`CHEVRON_CLOSED`](Self::CHEVRON_CLOSED) and [`CHEVRON_OPEN`](Self::CHEVRON_OPEN) as the
+/// [`chevrons`](crate::FileTree::chevrons) to avoid drawing any SVGs at all.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NerdFontIcons;
+
+impl NerdFontIcons {
+    /// The chevron drawn in front of closed directories.
+    pub const CHEVRON_CLOSED: Icon = glyph("\u{f460}");
+    /// The chevron drawn in front of open directories.
+    pub const CHEVRON_OPEN: Icon = glyph("\u{f47c}");
+}
+
+impl IconProvider for NerdFontIcons {
+    fn file_icon(&self, path: &Path) -> Option<Icon> {
+        let name = path.file_name()?.to_str()?;
+
+        let codepoint = match name {
+            ".gitattributes" | ".gitignore" | ".gitmodules" => "\u{e702}",
+            "Dockerfile" => "\u{f308}",
+            "Makefile" => "\u{e615}",
+            _ => match &*extension(path) {
+                "rs" => "\u{e7a8}",
+                "c" | "h" => "\u{e61e}",
+                "cc" | "cpp" | "hpp" => "\u{e61d}",
+                "css" | "scss" => "\u{e749}",
+                "go" => "\u{e724}",
+                "html" => "\u{e736}",
+                "java" => "\u{e738}",
+                "js" | "mjs" | "jsx" => "\u{e74e}",
+                "lua" => "\u{e620}",
+                "py" => "\u{e73c}",
+                "rb" => "\u{e739}",
+                "ts" | "tsx" => "\u{e628}",
+                "cs" | "kt" | "swift" | "wgsl" | "zig" => "\u{f1c9}",
+                "json" | "json5" | "jsonc" | "ron" => "\u{e60b}",
+                "cfg" | "conf" | "ini" | "toml" | "xml" | "yaml" | "yml" => "\u{e615}",
+                "markdown" | "md" | "mdx" => "\u{e73e}",
+                "avif" | "bmp" | "gif" | "ico" | "jpeg" | "jpg" | "png" | "svg" | "tiff"
+                | "webp" => "\u{f1c5}",
+                "7z" | "bz2" | "crate" | "gz" | "rar" | "tar" | "tgz" | "xz" | "zip" | "zst" => {
+                    "\u{f1c6}"
+                }
+                "flac" | "mp3" | "ogg" | "opus" | "wav" => "\u{f1c7}",
+                "mkv" | "mov" | "mp4" | "webm" => "\u{f1c8}",
+                "pdf" => "\u{f1c1}",
+                "lock" => "\u{f023}",
+                "bash" | "bat" | "cmd" | "fish" | "ps1" | "sh" | "zsh" => "\u{e795}",
+                "txt" => "\u{f0f6}",
+                _ => "\u{f016}",
+            },
+        };
+
+        Some(glyph(codepoint))
+    }
+
+    fn dir_icon(&self, path: &Path, open: bool) -> Option<Icon> {
+        let codepoint = match path.file_name()?.to_str()? {
+            ".git" => "\u{e5fb}",
+            ".github" => "\u{e5fd}",
+            _ if open => "\u{f07c}",
+            _ => "\u{f07b}",
+        };
+
+        Some(glyph(codepoint))
+    }
+}
+
+/// An [`IconProvider`] that draws emoji, which don't require any special fonts.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EmojiIcons;
+
+impl IconProvider for EmojiIcons {
+    fn file_icon(&self, path: &Path) -> Option<Icon> {
+        let codepoint = match &*extension(path) {
+            "rs" => "\u{1f980}",
+            "py" => "\u{1f40d}",
+            "c" | "cc" | "cpp" | "cs" | "css" | "go" | "h" | "hpp" | "html" | "java" | "js"
+            | "jsx" | "kt" | "lua" | "mjs" | "rb" | "scss" | "swift" | "ts" | "tsx" | "wgsl"
+            | "zig" => "\u{1f4dc}",
+            "cfg" | "conf" | "ini" | "json" | "json5" | "jsonc" | "ron" | "toml" | "xml"
+            | "yaml" | "yml" => "\u{2699}\u{fe0f}",
+            "markdown" | "md" | "mdx" => "\u{1f4dd}",
+            "avif" | "bmp" | "gif" | "ico" | "jpeg" | "jpg" | "png" | "svg" | "tiff" | "webp" => {
+                "\u{1f5bc}\u{fe0f}"
+            }
+            "7z" | "bz2" | "crate" | "gz" | "rar" | "tar" | "tgz" | "xz" | "zip" | "zst" => {
+                "\u{1f4e6}"
+            }
+            "flac" | "mp3" | "ogg" | "opus" | "wav" => "\u{1f3b5}",
+            "mkv" | "mov" | "mp4" | "webm" => "\u{1f3ac}",
+            "pdf" => "\u{1f4d5}",
+            "lock" => "\u{1f512}",
+            "bash" | "bat" | "cmd" | "fish" | "ps1" | "sh" | "zsh" => "\u{1f4bb}",
+            _ => "\u{1f4c4}",
+        };
+
+        Some(glyph(codepoint))
+    }
+
+    fn dir_icon(&self, _path: &Path, open: bool) -> Option<Icon> {
+        Some(glyph(if open { "\u{1f4c2}" } else { "\u{1f4c1}" }))
+    }
+}
+
+const fn glyph(glyph: &'static str) -> Icon {
+    Icon::Glyph(Cow::Borrowed(glyph))
+}
+
+fn extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default()
+}
@@ -2,16 +2,21 @@
 //!
 //! # Example
 //! ```no_run
+//! # use iced::Element;
+//! # use std::path::PathBuf;
 //! use iced::widget::scrollable;
 //! use iced_file_tree::file_tree;
 //!
+//! #[derive(Clone)]
 //! enum Message {
 //!     FileTreeMessage(PathBuf),
 //!     // ...
 //! }
+//! # struct State { path: PathBuf }
 //!
 //! fn view(state: &State) -> Element<'_, Message> {
 //!     let path: PathBuf = // ...
+//! #       state.path.clone();
 //!
 //!     scrollable(
 //!         file_tree(path)
@@ -21,9 +26,39 @@
 //! }
 //! ```
 
-mod dir;
-mod file;
+mod config;
+mod event;
 mod file_tree;
+mod icon;
+mod operation;
+#[cfg(feature = "serde")]
+mod snapshot;
+mod sort;
+mod state;
+pub mod style;
+mod tooltip;
+mod truncate;
+pub use config::{Guides, ToggleOn, Truncation};
+pub use event::{EntryKind, FileTreeEvent};
 pub use file_tree::{file_tree, FileTree};
+pub use iced::advanced::widget::Id;
+#[cfg(feature = "freedesktop")]
+pub use icon::FreedesktopIcons;
+#[cfg(feature = "icons")]
+pub use icon::{DefaultIcons, EmojiIcons, NerdFontIcons};
+pub use icon::{Icon, IconProvider};
+#[cfg(feature = "collation")]
+pub use icu_locale_core::{locale, Locale};
+pub use operation::{
+    cancel_expansion, collapse_all, collapse_siblings, expand, refresh, reveal, scroll_to, Align,
+    Limit,
+};
+#[cfg(feature = "serde")]
+pub use snapshot::{restore, snapshot, FileTreeSnapshot};
+pub use tooltip::describe;
+
+// only the snapshot tests serialize anything
+#[cfg(all(test, not(feature = "serde")))]
+use serde_json as _;
 
 const LINE_HEIGHT: f32 = 21.0;
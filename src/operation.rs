@@ -0,0 +1,353 @@
+use crate::state::State;
+use iced::{
+    advanced::widget::{
+        operate,
+        operation::{scrollable::AbsoluteOffset, Outcome, Scrollable},
+        Id, Operation,
+    },
+    Rectangle, Task, Vector,
+};
+use std::{any::Any, path::PathBuf};
+
+/// How far [`expand`] opens the directories below an entry.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Limit {
+    /// Every directory below the entry is opened.
+    #[default]
+    Unlimited,
+    /// Directories are opened up to the given number of levels, counting the entry itself.
+    Depth(usize),
+    /// Directories are opened level by level until the given number of entries has been added
+    /// to the tree.
+    Entries(usize),
+}
+
+/// Reveals the entry at the given path in the [`FileTree`](crate::FileTree) with the given [`Id`].
+///
+/// Opens every ancestor of the entry, selects it, and scrolls the enclosing
+/// [`Scrollable`](iced::widget::Scrollable) so it's visible. In controlled mode, each opened
+/// ancestor is published with [`FileTree::on_toggle`](crate::FileTree::on_toggle).
+pub fn reveal<T>(id: Id, path: impl Into<PathBuf>) -> Task<T>
+where
+    T: Send + 'static,
+{
+    struct Reveal {
+        target: Id,
+        path: PathBuf,
+    }
+
+    impl<T> Operation<T> for Reveal {
+        fn container(
+            &mut self,
+            _id: Option<&Id>,
+            _bounds: Rectangle,
+            operate_on_children: &mut dyn FnMut(&mut dyn Operation<T>),
+        ) {
+            operate_on_children(self);
+        }
+
+        fn custom(&mut self, state: &mut dyn Any, id: Option<&Id>) {
+            if id == Some(&self.target) {
+                if let Some(state) = state.downcast_mut::<State>() {
+                    state.reveal(&self.path);
+                }
+            }
+        }
+
+        fn finish(&self) -> Outcome<T> {
+            Outcome::Chain(Box::new(ScrollToRow::new(
+                self.target.clone(),
+                self.path.clone(),
+                Align::Nearest,
+            )))
+        }
+    }
+
+    operate(Reveal {
+        target: id,
+        path: path.into(),
+    })
+}
+
+/// Scrolls the [`Scrollable`](iced::widget::Scrollable) enclosing the
+/// [`FileTree`](crate::FileTree) with the given [`Id`] to the row of the entry at the given path,
+/// if it's visible.
+///
+/// Unlike [`reveal`], this doesn't open the ancestors of the entry or select it.
+pub fn scroll_to<T>(id: Id, path: impl Into<PathBuf>, align: Align) -> Task<T>
+where
+    T: Send + 'static,
+{
+    operate(ScrollToRow::new(id, path.into(), align))
+}
+
+/// Closes every directory of the [`FileTree`](crate::FileTree) with the given [`Id`] except the
+/// root, and cancels any running [`expand`].
+pub fn collapse_all<T>(id: Id) -> Task<T>
+where
+    T: Send + 'static,
+{
+    operate(Update::new(id, State::collapse_all))
+}
+
+/// Closes every directory next to the entry at the given path in the
+/// [`FileTree`](crate::FileTree) with the given [`Id`].
+pub fn collapse_siblings<T>(id: Id, path: impl Into<PathBuf>) -> Task<T>
+where
+    T: Send + 'static,
+{
+    let path = path.into();
+
+    operate(Update::new(id, move |state| state.collapse_siblings(&path)))
+}
+
+/// Recursively opens the directory at the given path in the [`FileTree`](crate::FileTree) with
+/// the given [`Id`], up to the given [`Limit`].
+///
+/// Like [`reveal`], this opens every ancestor of the directory first, so the opened directories
+/// are visible.
+///
+/// The directories are read a few at a time over the following frames, so expanding a huge
+/// subtree doesn't block the application. A running expansion can be stopped with
+/// [`cancel_expansion`]. In controlled mode, each opened directory is published with
+/// [`FileTree::on_toggle`](crate::FileTree::on_toggle).
+pub fn expand<T>(id: Id, path: impl Into<PathBuf>, limit: Limit) -> Task<T>
+where
+    T: Send + 'static,
+{
+    let path = path.into();
+
+    operate(Update::new(id, move |state| state.expand(&path, limit)))
+}
+
+/// Stops the running [`expand`] of the [`FileTree`](crate::FileTree) with the given [`Id`],
+/// keeping the directories it already opened.
+pub fn cancel_expansion<T>(id: Id) -> Task<T>
+where
+    T: Send + 'static,
+{
+    operate(Update::new(id, State::cancel_expansion))
+}
+
+/// Reads the directories of the [`FileTree`](crate::FileTree) with the given [`Id`] again if
+/// they changed on disk.
+///
+/// A directory is only read the first time it's shown, so this picks up entries that were
+/// created, removed or renamed since then, for example after a file watcher reported a change.
+pub fn refresh<T>(id: Id) -> Task<T>
+where
+    T: Send + 'static,
+{
+    operate(Update::new(id, State::refresh))
+}
+
+/// Applies a function to the [`State`] of the [`FileTree`](crate::FileTree) with the given [`Id`].
+struct Update<F> {
+    target: Id,
+    update: F,
+}
+
+impl<F> Update<F>
+where
+    F: FnMut(&mut State),
+{
+    fn new(target: Id, update: F) -> Self {
+        Self { target, update }
+    }
+}
+
+impl<T, F> Operation<T> for Update<F>
+where
+    F: FnMut(&mut State) + Send,
+{
+    fn container(
+        &mut self,
+        _id: Option<&Id>,
+        _bounds: Rectangle,
+        operate_on_children: &mut dyn FnMut(&mut dyn Operation<T>),
+    ) {
+        operate_on_children(self);
+    }
+
+    fn custom(&mut self, state: &mut dyn Any, id: Option<&Id>) {
+        if id == Some(&self.target) {
+            if let Some(state) = state.downcast_mut::<State>() {
+                (self.update)(state);
+            }
+        }
+    }
+}
+
+/// Where the row of an entry ends up after [`scroll_to`] scrolls to it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Align {
+    /// The row is scrolled to the top of the viewport.
+    Top,
+    /// The viewport is scrolled as little as possible to make the row visible.
+    #[default]
+    Nearest,
+}
+
+#[derive(Clone, Copy)]
+struct Viewport {
+    /// The index of the [`Scrollable`] in the order they're visited.
+    index: usize,
+    bounds: Rectangle,
+    content_bounds: Rectangle,
+    translation: Vector,
+}
+
+/// Scrolls the innermost [`Scrollable`] enclosing the [`FileTree`](crate::FileTree) with the
+/// given [`Id`] to the row of the given path.
+///
+/// The scrollables can't be scrolled while the operation is looking for the row, so this finds
+/// the offset and then chains a [`ScrollNth`] to scroll the right one.
+///
+/// The offset doesn't depend on the height of the content, and scrollables only clamp their
+/// offset when they're laid out, so this also works for rows rebuilt by the operation it's
+/// chained to, before the layout has caught up with them.
+pub struct ScrollToRow {
+    target: Id,
+    path: PathBuf,
+    align: Align,
+    scrollables: usize,
+    entered: Option<Viewport>,
+    stack: Vec<Viewport>,
+    bounds: Rectangle,
+    result: Option<(usize, AbsoluteOffset)>,
+}
+
+impl ScrollToRow {
+    pub fn new(target: Id, path: PathBuf, align: Align) -> Self {
+        Self {
+            target,
+            path,
+            align,
+            scrollables: 0,
+            entered: None,
+            stack: Vec::new(),
+            bounds: Rectangle::default(),
+            result: None,
+        }
+    }
+}
+
+impl<T> Operation<T> for ScrollToRow {
+    fn container(
+        &mut self,
+        _id: Option<&Id>,
+        bounds: Rectangle,
+        operate_on_children: &mut dyn FnMut(&mut dyn Operation<T>),
+    ) {
+        // a scrollable visits its content as a container right after visiting itself
+        let entered = self.entered.take();
+
+        if let Some(viewport) = entered {
+            self.stack.push(viewport);
+        }
+
+        self.bounds = bounds;
+        operate_on_children(self);
+
+        if entered.is_some() {
+            self.stack.pop();
+        }
+    }
+
+    fn scrollable(
+        &mut self,
+        _state: &mut dyn Scrollable,
+        _id: Option<&Id>,
+        bounds: Rectangle,
+        content_bounds: Rectangle,
+        translation: Vector,
+    ) {
+        self.entered = Some(Viewport {
+            index: self.scrollables,
+            bounds,
+            content_bounds,
+            translation,
+        });
+        self.scrollables += 1;
+    }
+
+    fn custom(&mut self, state: &mut dyn Any, id: Option<&Id>) {
+        if id != Some(&self.target) {
+            return;
+        }
+
+        let Some(state) = state.downcast_ref::<State>() else {
+            return;
+        };
+
+        let (Some(row), Some(viewport)) = (state.row_of(&self.path), self.stack.last()) else {
+            return;
+        };
+
+        let row_height = state.row_height();
+        let top = (row as f32).mul_add(row_height, self.bounds.y) - viewport.content_bounds.y;
+
+        let y = match self.align {
+            Align::Top => top,
+            Align::Nearest if top < viewport.translation.y => top,
+            Align::Nearest
+                if top + row_height > viewport.translation.y + viewport.bounds.height =>
+            {
+                top + row_height - viewport.bounds.height
+            }
+            Align::Nearest => return,
+        };
+
+        self.result = Some((
+            viewport.index,
+            AbsoluteOffset {
+                x: viewport.translation.x,
+                y,
+            },
+        ));
+    }
+
+    fn finish(&self) -> Outcome<T> {
+        match self.result {
+            Some((index, offset)) => Outcome::Chain(Box::new(ScrollNth {
+                index,
+                offset,
+                scrollables: 0,
+            })),
+            None => Outcome::None,
+        }
+    }
+}
+
+/// Scrolls the [`Scrollable`] visited at the given index to the given offset.
+struct ScrollNth {
+    index: usize,
+    offset: AbsoluteOffset,
+    scrollables: usize,
+}
+
+impl<T> Operation<T> for ScrollNth {
+    fn container(
+        &mut self,
+        _id: Option<&Id>,
+        _bounds: Rectangle,
+        operate_on_children: &mut dyn FnMut(&mut dyn Operation<T>),
+    ) {
+        operate_on_children(self);
+    }
+
+    fn scrollable(
+        &mut self,
+        state: &mut dyn Scrollable,
+        _id: Option<&Id>,
+        _bounds: Rectangle,
+        _content_bounds: Rectangle,
+        _translation: Vector,
+    ) {
+        if self.scrollables == self.index {
+            state.scroll_to(self.offset);
+        }
+
+        self.scrollables += 1;
+    }
+}
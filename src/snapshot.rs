@@ -0,0 +1,139 @@
+use crate::{
+    operation::{Align, ScrollToRow},
+    state::State,
+};
+use iced::{
+    advanced::widget::{operate, operation::Outcome, Id, Operation},
+    Rectangle, Task,
+};
+use serde::{Deserialize, Serialize};
+use std::{any::Any, collections::HashSet, path::PathBuf};
+
+/// The session state of a [`FileTree`](crate::FileTree), which can be saved and restored to make
+/// the tree look exactly as the user left it.
+///
+/// # Example
+/// ```no_run
+/// use iced::Task;
+/// use iced_file_tree::{FileTreeSnapshot, Id};
+///
+/// enum Message {
+///     Snapshot(FileTreeSnapshot),
+///     // ...
+/// }
+///
+/// // before exiting
+/// let task: Task<Message> = iced_file_tree::snapshot(Id::new("file tree")).map(Message::Snapshot);
+///
+/// // on startup
+/// # let snapshot = FileTreeSnapshot::default();
+/// let task: Task<Message> = iced_file_tree::restore(Id::new("file tree"), snapshot);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileTreeSnapshot {
+    /// The paths of the open directories.
+    pub expanded: HashSet<PathBuf>,
+    /// The path of the selected entry.
+    pub selected: Option<PathBuf>,
+    /// The path of the entry with keyboard focus.
+    pub focused: Option<PathBuf>,
+    /// The path of the first visible entry.
+    pub scroll_anchor: Option<PathBuf>,
+    /// Whether hidden files were shown, see
+    /// [`FileTree::hidden_files`](crate::FileTree::hidden_files).
+    pub show_hidden: bool,
+    /// Whether file extensions were shown, see
+    /// [`FileTree::file_extensions`](crate::FileTree::file_extensions).
+    pub show_extensions: bool,
+    /// The locale whose collation rules were used to sort entries, if any.
+    pub locale: Option<String>,
+}
+
+/// Produces a [`FileTreeSnapshot`] of the [`FileTree`](crate::FileTree) with the given [`Id`].
+pub fn snapshot(id: Id) -> Task<FileTreeSnapshot> {
+    struct Snapshot {
+        target: Id,
+        snapshot: Option<FileTreeSnapshot>,
+    }
+
+    impl Operation<FileTreeSnapshot> for Snapshot {
+        fn container(
+            &mut self,
+            _id: Option<&Id>,
+            _bounds: Rectangle,
+            operate_on_children: &mut dyn FnMut(&mut dyn Operation<FileTreeSnapshot>),
+        ) {
+            operate_on_children(self);
+        }
+
+        fn custom(&mut self, state: &mut dyn Any, id: Option<&Id>) {
+            if id == Some(&self.target) {
+                if let Some(state) = state.downcast_ref::<State>() {
+                    self.snapshot = Some(state.snapshot());
+                }
+            }
+        }
+
+        fn finish(&self) -> Outcome<FileTreeSnapshot> {
+            self.snapshot.clone().map_or(Outcome::None, Outcome::Some)
+        }
+    }
+
+    operate(Snapshot {
+        target: id,
+        snapshot: None,
+    })
+}
+
+/// Restores the open directories, selection, focus, scroll position and sort and filter settings
+/// of a [`FileTreeSnapshot`] in the [`FileTree`](crate::FileTree) with the given [`Id`].
+///
+/// The restored settings replace those of the [`FileTree`](crate::FileTree) until the
+/// application changes them. In controlled mode, the open directories are owned by the
+/// application, so they have to be restored by it.
+pub fn restore<T>(id: Id, snapshot: FileTreeSnapshot) -> Task<T>
+where
+    T: Send + 'static,
+{
+    struct Restore {
+        target: Id,
+        snapshot: FileTreeSnapshot,
+    }
+
+    impl<T> Operation<T> for Restore {
+        fn container(
+            &mut self,
+            _id: Option<&Id>,
+            _bounds: Rectangle,
+            operate_on_children: &mut dyn FnMut(&mut dyn Operation<T>),
+        ) {
+            operate_on_children(self);
+        }
+
+        fn custom(&mut self, state: &mut dyn Any, id: Option<&Id>) {
+            if id == Some(&self.target) {
+                if let Some(state) = state.downcast_mut::<State>() {
+                    state.restore(&self.snapshot);
+                }
+            }
+        }
+
+        fn finish(&self) -> Outcome<T> {
+            self.snapshot
+                .scroll_anchor
+                .clone()
+                .map_or(Outcome::None, |anchor| {
+                    Outcome::Chain(Box::new(ScrollToRow::new(
+                        self.target.clone(),
+                        anchor,
+                        Align::Top,
+                    )))
+                })
+        }
+    }
+
+    operate(Restore {
+        target: id,
+        snapshot,
+    })
+}
@@ -0,0 +1,174 @@
+#[cfg(feature = "collation")]
+use crate::Locale;
+#[cfg(feature = "collation")]
+use icu_collator::{options::CollatorOptions, Collator, CollatorBorrowed};
+use std::cmp::Ordering;
+#[cfg(feature = "collation")]
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use unicase::UniCase;
+
+/// The order in which entries of a directory are listed.
+#[derive(Clone, Default)]
+pub enum NameOrder {
+    /// Case-insensitive ordering using full Unicode case folding.
+    #[default]
+    CaseFolded,
+    /// Locale-aware ordering using the Unicode Collation Algorithm.
+    #[cfg(feature = "collation")]
+    Collated(Rc<CollatorBorrowed<'static>>, Locale),
+}
+
+impl NameOrder {
+    /// Returns the ordering of the given locale, or `None` if no collation data is available for
+    /// it.
+    ///
+    /// Loading the collation data is expensive and the [`FileTree`](crate::FileTree) is built
+    /// again in every `view`, so collators are shared between calls.
+    #[cfg(feature = "collation")]
+    pub fn collated(locale: Locale) -> Option<Self> {
+        thread_local! {
+            static COLLATORS: RefCell<HashMap<Locale, Rc<CollatorBorrowed<'static>>>> =
+                RefCell::default();
+        }
+
+        let collator = COLLATORS.with_borrow_mut(|collators| {
+            if let Some(collator) = collators.get(&locale) {
+                return Some(collator.clone());
+            }
+
+            let collator =
+                Rc::new(Collator::try_new((&locale).into(), CollatorOptions::default()).ok()?);
+            collators.insert(locale.clone(), collator.clone());
+
+            Some(collator)
+        })?;
+
+        Some(Self::Collated(collator, locale))
+    }
+
+    /// Compares two entry names, falling back to their raw ordering if they're otherwise equal so
+    /// the resulting order doesn't depend on the order the file system returned them in.
+    pub fn cmp(&self, a: &str, b: &str) -> Ordering {
+        match self {
+            Self::CaseFolded => UniCase::new(a).cmp(&UniCase::new(b)),
+            #[cfg(feature = "collation")]
+            Self::Collated(collator, _) => collator.compare(a, b),
+        }
+        .then_with(|| a.cmp(b))
+    }
+
+    /// Returns the locale whose collation rules are used, if any.
+    pub fn locale(&self) -> Option<String> {
+        match self {
+            Self::CaseFolded => None,
+            #[cfg(feature = "collation")]
+            Self::Collated(_, locale) => Some(locale.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // written with escapes to keep the source ASCII
+    const UBER: &str = "\u{dc}ber";
+    const UBEN: &str = "\u{fc}ben";
+    const ECRAN: &str = "\u{c9}cran";
+    const ECRIN: &str = "\u{e9}crin";
+    const OL: &str = "\u{f6}l";
+    const STRASSE: &str = "Stra\u{df}e";
+    const ARBUZ: &str = "\u{410}\u{440}\u{431}\u{443}\u{437}";
+    const BANAN: &str = "\u{431}\u{430}\u{43d}\u{430}\u{43d}";
+    const YABLOKO: &str = "\u{44f}\u{431}\u{43b}\u{43e}\u{43a}\u{43e}";
+
+    fn sorted<'a>(order: &NameOrder, names: &[&'a str]) -> Vec<&'a str> {
+        let mut names = names.to_vec();
+        names.sort_by(|a, b| order.cmp(a, b));
+        names
+    }
+
+    #[test]
+    fn case_folded_ignores_case() {
+        assert_eq!(
+            sorted(&NameOrder::CaseFolded, &["cherry", "Banana", "apple"]),
+            ["apple", "Banana", "cherry"]
+        );
+    }
+
+    #[test]
+    fn case_folded_folds_non_ascii() {
+        assert_eq!(
+            sorted(&NameOrder::CaseFolded, &[UBER, ECRIN, ECRAN, UBEN]),
+            [ECRAN, ECRIN, UBEN, UBER]
+        );
+        assert_eq!(
+            sorted(&NameOrder::CaseFolded, &[YABLOKO, ARBUZ, BANAN]),
+            [ARBUZ, BANAN, YABLOKO]
+        );
+        // without collation, accented letters sort after every ASCII letter
+        assert_eq!(
+            sorted(&NameOrder::CaseFolded, &[OL, "zebra"]),
+            ["zebra", OL]
+        );
+    }
+
+    #[test]
+    fn equal_names_keep_a_stable_order() {
+        assert_eq!(
+            sorted(&NameOrder::CaseFolded, &["readme", "README", "ReadMe"]),
+            ["README", "ReadMe", "readme"]
+        );
+        assert_eq!(
+            NameOrder::CaseFolded.cmp(STRASSE, "STRASSE"),
+            Ordering::Greater
+        );
+    }
+
+    #[cfg(feature = "collation")]
+    fn collated(locale: Locale) -> NameOrder {
+        NameOrder::collated(locale).unwrap()
+    }
+
+    #[cfg(feature = "collation")]
+    #[test]
+    fn collated_sorts_accents_with_their_base_letters() {
+        assert_eq!(
+            sorted(
+                &collated(crate::locale!("en")),
+                &["zebra", UBER, ECRAN, "apple", "ecrou"]
+            ),
+            ["apple", ECRAN, "ecrou", UBER, "zebra"]
+        );
+    }
+
+    #[cfg(feature = "collation")]
+    #[test]
+    fn collated_follows_the_locale() {
+        assert_eq!(
+            sorted(&collated(crate::locale!("en")), &[OL, "zebra", ARBUZ]),
+            [OL, "zebra", ARBUZ]
+        );
+        assert_eq!(
+            sorted(&collated(crate::locale!("sv")), &[OL, "zebra"]),
+            ["zebra", OL]
+        );
+        assert_eq!(
+            sorted(&collated(crate::locale!("ru")), &[YABLOKO, ARBUZ, BANAN]),
+            [ARBUZ, BANAN, YABLOKO]
+        );
+    }
+
+    #[cfg(feature = "collation")]
+    #[test]
+    fn collated_shares_collators() {
+        let (NameOrder::Collated(a, _), NameOrder::Collated(b, _)) = (
+            collated(crate::locale!("de")),
+            collated(crate::locale!("de")),
+        ) else {
+            unreachable!();
+        };
+
+        assert!(Rc::ptr_eq(&a, &b));
+    }
+}
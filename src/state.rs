@@ -0,0 +1,1375 @@
+#[cfg(feature = "serde")]
+use crate::FileTreeSnapshot;
+use crate::{
+    config::{Config, RowOptions},
+    sort::NameOrder,
+    style::Catalog,
+    truncate::{measure, truncate},
+    EntryKind, Limit, Truncation,
+};
+use iced::{
+    advanced::{mouse::Click, text, widget::operation::Focusable},
+    keyboard::Modifiers,
+    time::Instant,
+    touch::Finger,
+    Font, Pixels, Point,
+};
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet, VecDeque},
+    fs::DirEntry,
+    iter::once,
+    path::{Path, PathBuf},
+    rc::Rc,
+    time::SystemTime,
+};
+
+/// A file or directory that has been seen by the [`FileTree`](crate::FileTree).
+pub struct Entry {
+    pub path: PathBuf,
+    pub name: String,
+    pub is_dir: bool,
+    /// Whether the entry is a directory without visible entries, as of the time it was last read.
+    empty: Cell<bool>,
+    /// The width of the name, with the font and text size it was measured with.
+    width: Cell<Option<(Font, Pixels, f32)>>,
+    truncated: RefCell<Option<Truncated>>,
+}
+
+/// The name of an [`Entry`] shortened to fit in a row, with the parameters it was shortened with.
+struct Truncated {
+    font: Font,
+    size: Pixels,
+    truncation: Truncation,
+    max_width: f32,
+    name: String,
+}
+
+impl Entry {
+    fn new(path: PathBuf, is_dir: bool, options: RowOptions) -> Self {
+        let name = if is_dir || options.show_extensions {
+            path.file_name()
+        } else {
+            path.file_stem()
+        }
+        .map_or_else(|| path.to_string_lossy(), |name| name.to_string_lossy())
+        .into_owned();
+        let empty = is_dir && is_empty_dir(&path, options.show_hidden);
+
+        Self {
+            path,
+            name,
+            is_dir,
+            empty: Cell::new(empty),
+            width: Cell::new(None),
+            truncated: RefCell::new(None),
+        }
+    }
+
+    /// Returns the width of the name, measuring it again if the font or text size changed.
+    pub fn name_width<Renderer>(&self, font: Font, size: Pixels) -> f32
+    where
+        Renderer: text::Renderer<Font = Font>,
+    {
+        if let Some((measured_font, measured_size, width)) = self.width.get() {
+            if measured_font == font && measured_size == size {
+                return width;
+            }
+        }
+
+        let width = measure::<Renderer>(&self.name, font, size);
+
+        self.width.set(Some((font, size, width)));
+
+        width
+    }
+
+    /// Returns the name shortened to fit in the given width, reusing the last result if it was
+    /// shortened the same way.
+    pub fn truncated_name<Renderer>(
+        &self,
+        font: Font,
+        size: Pixels,
+        truncation: Truncation,
+        max_width: f32,
+    ) -> String
+    where
+        Renderer: text::Renderer<Font = Font>,
+    {
+        if truncation == Truncation::Clip || self.name_width::<Renderer>(font, size) <= max_width {
+            return self.name.clone();
+        }
+
+        if let Some(truncated) = &*self.truncated.borrow() {
+            if truncated.font == font
+                && truncated.size == size
+                && truncated.truncation == truncation
+                && truncated.max_width == max_width
+            {
+                return truncated.name.clone();
+            }
+        }
+
+        let name = truncate::<Renderer>(&self.name, font, size, truncation, max_width);
+
+        self.truncated.replace(Some(Truncated {
+            font,
+            size,
+            truncation,
+            max_width,
+            name: name.clone(),
+        }));
+
+        name
+    }
+
+    /// Whether the entry is a directory without visible entries, which doesn't draw a chevron.
+    pub fn is_empty(&self) -> bool {
+        self.empty.get()
+    }
+
+    /// Whether dropping the entry into the given directory would move it anywhere, which it
+    /// wouldn't in its own directory or below itself.
+    pub fn moves_into(&self, dir: &Path) -> bool {
+        !dir.starts_with(&self.path) && self.path.parent() != Some(dir)
+    }
+
+    pub fn kind(&self) -> EntryKind {
+        if self.is_dir {
+            EntryKind::Directory
+        } else {
+            EntryKind::File
+        }
+    }
+}
+
+/// The sorted entries of a directory, as of the time it was last modified, see
+/// [`refresh`](State::refresh).
+struct Listing {
+    modified: Option<SystemTime>,
+    entries: Rc<[Rc<Entry>]>,
+}
+
+/// A recursive expansion that's carried out a few directories at a time, so opening a huge subtree
+/// doesn't block the application.
+struct Expansion {
+    /// The directories left to open, with their depth below the directory being expanded.
+    queue: VecDeque<(PathBuf, usize)>,
+    max_depth: Option<usize>,
+    /// The number of entries that may still be added to the tree.
+    budget: Option<usize>,
+}
+
+/// The number of directories opened by an [`Expansion`] per event.
+const EXPANSION_STEP: usize = 32;
+
+/// A visible row of the [`FileTree`](crate::FileTree).
+#[derive(Clone)]
+pub struct Row {
+    pub entry: Rc<Entry>,
+    pub depth: usize,
+    /// The row of the directory containing the entry.
+    pub parent: Option<usize>,
+    /// The row following the last row below the entry.
+    pub end: usize,
+    /// The directories before the entry in a chain of compact folders, see
+    /// [`FileTree::compact_folders`](crate::FileTree::compact_folders).
+    pub chain: Vec<Rc<Entry>>,
+}
+
+/// Separates the names of the directories in a chain of compact folders.
+pub const SEPARATOR: &str = "/";
+
+impl Row {
+    /// Returns the directories in the chain of the row, followed by its entry.
+    pub fn segments(&self) -> impl Iterator<Item = &Rc<Entry>> {
+        self.chain.iter().chain(once(&self.entry))
+    }
+
+    pub fn contains(&self, path: &Path) -> bool {
+        self.segments().any(|segment| segment.path == path)
+    }
+
+    /// Returns the name shown in the row, which includes the names of the chain.
+    pub fn name(&self) -> Cow<'_, str> {
+        if self.chain.is_empty() {
+            Cow::Borrowed(&self.entry.name)
+        } else {
+            Cow::Owned(
+                self.segments()
+                    .map(|segment| segment.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(SEPARATOR),
+            )
+        }
+    }
+}
+
+/// A finger on the [`FileTree`](crate::FileTree).
+pub struct Touch {
+    pub finger: Finger,
+    /// Where the finger was put down, in window coordinates.
+    pub origin: Point,
+    pub pressed_at: Instant,
+    /// The entry the finger was put down on.
+    pub entry: Rc<Entry>,
+    pub gesture: Gesture,
+}
+
+/// The last tap on the [`FileTree`](crate::FileTree), to recognize double taps.
+pub struct Tap {
+    /// Where the finger was lifted, in window coordinates.
+    pub position: Point,
+    pub at: Instant,
+}
+
+/// An entry being dragged with the mouse.
+pub struct Drag {
+    /// Where the button was pressed, in window coordinates.
+    pub origin: Point,
+    /// The entry the button was pressed on.
+    pub entry: Rc<Entry>,
+    /// Either [`Gesture::Pending`] or [`Gesture::Dragging`].
+    pub gesture: Gesture,
+}
+
+/// What a [`Touch`] or [`Drag`] turned out to be so far.
+pub enum Gesture {
+    /// The finger or cursor hasn't moved far enough or been held down long enough yet.
+    Pending,
+    /// The finger was held down without moving.
+    Held,
+    /// The finger moved after being held down, or the cursor moved with the button held down,
+    /// dragging the entry onto the given directory.
+    Dragging(Option<Rc<Entry>>),
+    /// The finger moved before being held down, which scrolls instead.
+    Scrolling,
+}
+
+/// The sort and filter settings of a restored [`FileTreeSnapshot`], which replace those of the
+/// [`FileTree`](crate::FileTree) until the application changes them.
+#[cfg(feature = "serde")]
+struct Restored {
+    show_hidden: bool,
+    show_extensions: bool,
+    order: NameOrder,
+    /// The settings of the [`FileTree`](crate::FileTree) when the snapshot was restored.
+    replaced: Option<(RowOptions, Option<String>)>,
+}
+
+/// The state of a [`FileTree`](crate::FileTree), keyed by path so it stays attached to the
+/// correct entries when their siblings change.
+#[derive(Default)]
+pub struct State {
+    root: PathBuf,
+    /// The entry of the root, which is kept between rebuilds so it's only read and measured again
+    /// when the root or the settings change.
+    root_entry: Option<Rc<Entry>>,
+    options: RowOptions,
+    order: NameOrder,
+    #[cfg(feature = "serde")]
+    restored: Option<Restored>,
+    row_height: f32,
+    dirty: bool,
+    /// Whether the rows were rebuilt outside of layout, so the layout is out of date.
+    relayout: bool,
+    open: HashSet<PathBuf>,
+    listings: HashMap<PathBuf, Listing>,
+    rows: Vec<Row>,
+    selected: Option<PathBuf>,
+    /// The row of the selected entry, if it's visible.
+    selected_row: Option<usize>,
+    focused: Option<PathBuf>,
+    hovered: Option<Rc<Entry>>,
+    /// When the cursor entered the row of the hovered entry, if its tooltip may still be shown.
+    hovered_at: Option<Instant>,
+    pub tooltip: Option<String>,
+    /// The first row intersecting the viewport when the tree was last drawn.
+    first_visible: Cell<usize>,
+    expansion: Option<Expansion>,
+    /// The directories opened or closed by operations, which haven't been published yet.
+    toggled: Vec<(PathBuf, bool)>,
+    pub last_click: Option<Click>,
+    pub modifiers: Modifiers,
+    pub touch: Option<Touch>,
+    pub last_tap: Option<Tap>,
+    pub drag: Option<Drag>,
+}
+
+impl State {
+    /// Makes sure the visible rows match the settings of the [`FileTree`](crate::FileTree) and
+    /// the open directories, returning whether they were rebuilt.
+    pub fn sync<Message, Theme>(&mut self, root: &Path, config: &Config<'_, Message, Theme>) -> bool
+    where
+        Theme: Catalog,
+    {
+        #[cfg(feature = "serde")]
+        let (options, order) = self
+            .restored_settings(config)
+            .unwrap_or_else(|| (config.rows, config.order.clone()));
+        #[cfg(not(feature = "serde"))]
+        let (options, order) = (config.rows, config.order.clone());
+        let changed = self.rows.is_empty()
+            || self.root != root
+            || self.options != options
+            || self.order.locale() != order.locale();
+
+        if changed {
+            self.root = root.to_path_buf();
+            self.root_entry = Some(Rc::new(Entry::new(root.to_path_buf(), true, options)));
+            self.options = options;
+            self.order = order;
+            self.listings.clear();
+        }
+
+        self.row_height = config.row_height;
+
+        // in controlled mode, the open directories are owned by the application
+        let expanded = match config.expanded {
+            Some(expanded) if expanded != &self.open => {
+                let previous = std::mem::replace(&mut self.open, expanded.clone());
+
+                // changes made by operations stay until the application has been told about them
+                for (path, open) in &self.toggled {
+                    if *open {
+                        self.open.insert(path.clone());
+                    } else {
+                        self.open.remove(path);
+                    }
+                }
+
+                self.open != previous
+            }
+            _ => false,
+        };
+
+        let rebuild = changed || expanded || self.dirty;
+
+        if rebuild {
+            self.rebuild();
+        }
+
+        rebuild
+    }
+
+    pub fn row_height(&self) -> f32 {
+        self.row_height
+    }
+
+    pub fn rows(&self) -> &[Row] {
+        &self.rows
+    }
+
+    pub fn entry(&self, row: usize) -> &Entry {
+        &self.rows[row].entry
+    }
+
+    pub fn is_open(&self, row: usize) -> bool {
+        self.open.contains(&self.rows[row].entry.path)
+    }
+
+    pub fn row_of(&self, path: &Path) -> Option<usize> {
+        self.rows.iter().position(|row| row.contains(path))
+    }
+
+    pub fn is_selected(&self, row: usize) -> bool {
+        self.selected_row == Some(row)
+    }
+
+    pub fn selected_row(&self) -> Option<usize> {
+        self.selected_row
+    }
+
+    pub fn is_focused(&self, row: usize) -> bool {
+        self.focused
+            .as_ref()
+            .is_some_and(|focused| self.rows[row].contains(focused))
+    }
+
+    pub fn focused_row(&self) -> Option<usize> {
+        self.focused
+            .as_ref()
+            .and_then(|focused| self.row_of(focused))
+    }
+
+    /// Selects the entry at the given path in the given row and gives it keyboard focus,
+    /// returning whether the selection changed.
+    pub fn select(&mut self, row: usize, path: &Path) -> bool {
+        debug_assert!(self.rows[row].contains(path));
+
+        let path = path.to_path_buf();
+        let changed = self.selected.as_ref() != Some(&path);
+
+        self.selected = Some(path.clone());
+        self.selected_row = Some(row);
+        self.focused = Some(path);
+
+        changed
+    }
+
+    /// Returns the gesture of the touch or mouse drag in progress, if any.
+    fn gesture(&self) -> Option<&Gesture> {
+        self.touch
+            .as_ref()
+            .map(|touch| &touch.gesture)
+            .or_else(|| self.drag.as_ref().map(|drag| &drag.gesture))
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        matches!(self.gesture(), Some(Gesture::Dragging(_)))
+    }
+
+    /// Whether an entry is being dragged onto the directory in the given row.
+    pub fn is_drop_target(&self, row: usize) -> bool {
+        match self.gesture() {
+            Some(Gesture::Dragging(Some(target))) => target.path == self.rows[row].entry.path,
+            _ => false,
+        }
+    }
+
+    pub fn hovered(&self) -> Option<&Path> {
+        self.hovered.as_ref().map(|entry| entry.path.as_path())
+    }
+
+    pub fn hovered_at(&self) -> Option<Instant> {
+        self.hovered_at
+    }
+
+    /// Hides the tooltip until a different entry is hovered.
+    pub fn hide_tooltip(&mut self) {
+        self.hovered_at = None;
+        self.tooltip = None;
+    }
+
+    /// Remembers the entry in the given row as the hovered one, returning the previously hovered
+    /// entry.
+    pub fn hover(&mut self, row: Option<usize>) -> Option<Rc<Entry>> {
+        let entry = row.map(|row| self.rows[row].entry.clone());
+
+        self.hovered_at = row.map(|_| Instant::now());
+        self.tooltip = None;
+
+        std::mem::replace(&mut self.hovered, entry)
+    }
+
+    /// Opens every ancestor of the entry at the given path and selects it, rebuilding the rows
+    /// the next time the tree is synced.
+    pub fn reveal(&mut self, path: &Path) {
+        if !self.open_ancestors(path) {
+            return;
+        }
+
+        self.selected = Some(path.to_path_buf());
+        self.dirty = true;
+    }
+
+    /// Opens every ancestor of the entry at the given path, returning whether it's below the
+    /// root.
+    fn open_ancestors(&mut self, path: &Path) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+
+        let mut ancestor = self.root.clone();
+        let ancestors = relative.parent().into_iter().flatten().map(|component| {
+            ancestor.push(component);
+            ancestor.clone()
+        });
+
+        for ancestor in once(self.root.clone()).chain(ancestors) {
+            if self.open.insert(ancestor.clone()) {
+                self.toggled.push((ancestor, true));
+            }
+        }
+
+        true
+    }
+
+    /// Closes every directory except the root.
+    pub fn collapse_all(&mut self) {
+        let (root, toggled) = (&self.root, &mut self.toggled);
+
+        self.expansion = None;
+        self.open.retain(|path| {
+            let keep = path == root;
+
+            if !keep {
+                toggled.push((path.clone(), false));
+            }
+
+            keep
+        });
+        self.dirty = true;
+    }
+
+    /// Closes every directory next to the entry at the given path.
+    pub fn collapse_siblings(&mut self, path: &Path) {
+        let (parent, toggled) = (path.parent(), &mut self.toggled);
+
+        self.open.retain(|open| {
+            let keep = open.parent() != parent || open == path;
+
+            if !keep {
+                toggled.push((open.clone(), false));
+            }
+
+            keep
+        });
+        self.dirty = true;
+    }
+
+    /// Forgets the entries of the directories that changed on disk since they were read, so
+    /// they're read again the next time the tree is synced.
+    pub fn refresh(&mut self) {
+        self.listings
+            .retain(|path, listing| listing.modified == modified(path));
+
+        // a directory can become empty without its parent changing
+        let listed = self
+            .listings
+            .values()
+            .flat_map(|listing| listing.entries.iter());
+
+        for entry in self
+            .root_entry
+            .iter()
+            .chain(listed)
+            .filter(|entry| entry.is_dir)
+        {
+            entry.empty.set(self.listings.get(&entry.path).map_or_else(
+                || is_empty_dir(&entry.path, self.options.show_hidden),
+                |listing| listing.entries.is_empty(),
+            ));
+        }
+
+        self.dirty = true;
+    }
+
+    /// Opens every ancestor of the directory at the given path, and starts opening it and the
+    /// directories below it.
+    pub fn expand(&mut self, path: &Path, limit: Limit) {
+        if !path.is_dir() || !self.open_ancestors(path) {
+            return;
+        }
+
+        let (max_depth, budget) = match limit {
+            Limit::Unlimited => (None, None),
+            Limit::Depth(depth) => (Some(depth), None),
+            Limit::Entries(entries) => (None, Some(entries)),
+        };
+
+        self.expansion = Some(Expansion {
+            queue: VecDeque::from([(path.to_path_buf(), 0)]),
+            max_depth,
+            budget,
+        });
+        self.dirty = true;
+    }
+
+    /// Stops the running expansion, keeping the directories it already opened.
+    pub fn cancel_expansion(&mut self) {
+        self.expansion = None;
+    }
+
+    /// Whether there are pending changes made by operations.
+    pub fn is_busy(&self) -> bool {
+        self.expansion.is_some() || !self.toggled.is_empty()
+    }
+
+    /// Opens the next few directories of the running expansion, if any.
+    pub fn expand_step(&mut self) {
+        let Some(mut expansion) = self.expansion.take() else {
+            return;
+        };
+
+        for _ in 0..EXPANSION_STEP {
+            let Some((path, depth)) = expansion.queue.pop_front() else {
+                break;
+            };
+
+            if expansion
+                .max_depth
+                .is_some_and(|max_depth| depth >= max_depth)
+            {
+                continue;
+            }
+
+            // a chain of compact folders shares a row, so it's opened at once and counts as a
+            // single directory
+            let compactable = self.options.compact_folders && path != self.root;
+            let mut chain = vec![path];
+
+            let entries = loop {
+                let entries = self.listing(&chain[chain.len() - 1]);
+
+                match &*entries {
+                    [child] if compactable && child.is_dir => chain.push(child.path.clone()),
+                    _ => break entries,
+                }
+            };
+
+            if let Some(budget) = &mut expansion.budget {
+                // stop at the first directory that doesn't fit, so the tree is expanded evenly
+                let Some(remaining) = budget.checked_sub(entries.len()) else {
+                    expansion.queue.clear();
+                    break;
+                };
+
+                *budget = remaining;
+            }
+
+            expansion.queue.extend(
+                entries
+                    .iter()
+                    .filter(|entry| entry.is_dir)
+                    .map(|entry| (entry.path.clone(), depth + 1)),
+            );
+
+            for path in chain {
+                if self.open.insert(path.clone()) {
+                    self.toggled.push((path, true));
+                }
+            }
+        }
+
+        self.dirty = true;
+
+        if !expansion.queue.is_empty() {
+            self.expansion = Some(expansion);
+        }
+    }
+
+    /// Marks the layout as out of date until [`take_relayout`](Self::take_relayout) is called.
+    pub fn invalidate_layout(&mut self) {
+        self.relayout = true;
+    }
+
+    /// Returns whether the layout is out of date, resetting it.
+    pub fn take_relayout(&mut self) -> bool {
+        std::mem::take(&mut self.relayout)
+    }
+
+    /// Takes the directories opened or closed by operations since this was last called.
+    pub fn take_toggled(&mut self) -> Vec<(PathBuf, bool)> {
+        std::mem::take(&mut self.toggled)
+    }
+
+    pub fn set_first_visible(&self, row: usize) {
+        self.first_visible.set(row);
+    }
+
+    /// Opens or closes the directory in the given row.
+    pub fn toggle(&mut self, row: usize) {
+        let row = &self.rows[row];
+        let open = !self.open.contains(&row.entry.path);
+        debug_assert!(row.entry.is_dir);
+
+        // a chain of compact folders is opened and closed at once
+        for segment in row.segments() {
+            if open {
+                self.open.insert(segment.path.clone());
+            } else {
+                self.open.remove(&segment.path);
+            }
+        }
+
+        self.rebuild();
+    }
+
+    /// Flattens the open directories into the list of visible rows.
+    fn rebuild(&mut self) {
+        let Some(root) = self.root_entry.clone() else {
+            return;
+        };
+
+        self.rows.clear();
+        self.push_rows(root, 0, None);
+
+        self.dirty = false;
+        self.selected_row = self
+            .selected
+            .as_ref()
+            .and_then(|selected| self.row_of(selected));
+    }
+
+    fn push_rows(&mut self, entry: Rc<Entry>, depth: usize, parent: Option<usize>) {
+        let row = self.rows.len();
+        let (chain, entry) = self.compact(entry, parent.is_some());
+        let open = (entry.is_dir && self.open.contains(&entry.path)).then(|| entry.path.clone());
+
+        self.rows.push(Row {
+            entry,
+            depth,
+            parent,
+            end: row + 1,
+            chain,
+        });
+
+        if let Some(path) = open {
+            let children = self.listing(&path);
+            self.rows[row].entry.empty.set(children.is_empty());
+
+            for child in children.iter() {
+                self.push_rows(child.clone(), depth + 1, Some(row));
+            }
+        }
+
+        self.rows[row].end = self.rows.len();
+    }
+
+    /// Follows the chain of directories that only contain another directory starting at the
+    /// given entry, returning the chain and the directory at its end.
+    fn compact(&mut self, mut entry: Rc<Entry>, compactable: bool) -> (Vec<Rc<Entry>>, Rc<Entry>) {
+        let mut chain = Vec::new();
+
+        if !compactable || !self.options.compact_folders {
+            return (chain, entry);
+        }
+
+        while entry.is_dir {
+            let listing = self.listing(&entry.path);
+
+            let [child] = &*listing else {
+                break;
+            };
+
+            if !child.is_dir {
+                break;
+            }
+
+            chain.push(std::mem::replace(&mut entry, child.clone()));
+        }
+
+        (chain, entry)
+    }
+
+    /// Returns the entries of a directory, reading them if they haven't been read yet.
+    fn listing(&mut self, path: &Path) -> Rc<[Rc<Entry>]> {
+        if let Some(listing) = self.listings.get(path) {
+            return listing.entries.clone();
+        }
+
+        let modified = modified(path);
+
+        let (mut dirs, mut files) = (Vec::new(), Vec::new());
+
+        if let Ok(read_dir) = std::fs::read_dir(path) {
+            for child in read_dir.filter_map(Result::ok) {
+                let name = child.file_name().to_string_lossy().into_owned();
+
+                if !self.options.show_hidden && name.starts_with('.') {
+                    continue;
+                }
+
+                match child.file_type() {
+                    Ok(t) if t.is_dir() => dirs.push((child, name)),
+                    Ok(t) if t.is_file() => files.push((child, name)),
+                    _ => {}
+                }
+            }
+        }
+
+        let (options, order) = (self.options, &self.order);
+        let sorted = |mut entries: Vec<(DirEntry, String)>, is_dir: bool| {
+            entries.sort_by(|(_, aname), (_, bname)| order.cmp(aname, bname));
+            entries
+                .into_iter()
+                .map(move |(child, _)| Rc::new(Entry::new(child.path(), is_dir, options)))
+        };
+
+        let entries = sorted(dirs, true)
+            .chain(sorted(files, false))
+            .collect::<Rc<[_]>>();
+
+        self.listings.insert(
+            path.to_path_buf(),
+            Listing {
+                modified,
+                entries: entries.clone(),
+            },
+        );
+
+        entries
+    }
+}
+
+/// Lets the [`FileTree`](crate::FileTree) take part in focus operations, so focusing another
+/// widget stops it from handling the keyboard.
+impl Focusable for State {
+    fn is_focused(&self) -> bool {
+        self.focused.is_some()
+    }
+
+    fn focus(&mut self) {
+        if self.focused.is_none() {
+            self.focused = self
+                .selected
+                .clone()
+                .or_else(|| self.rows.first().map(|row| row.entry.path.clone()));
+        }
+    }
+
+    fn unfocus(&mut self) {
+        self.focused = None;
+    }
+}
+
+#[cfg(feature = "serde")]
+impl State {
+    pub fn snapshot(&self) -> FileTreeSnapshot {
+        FileTreeSnapshot {
+            expanded: self.open.clone(),
+            selected: self.selected.clone(),
+            focused: self.focused.clone(),
+            scroll_anchor: self
+                .rows
+                .get(self.first_visible.get())
+                .map(|row| row.entry.path.clone()),
+            show_hidden: self.options.show_hidden,
+            show_extensions: self.options.show_extensions,
+            locale: self.order.locale(),
+        }
+    }
+
+    /// Returns the sort and filter settings of the restored snapshot, unless the application
+    /// changed its own since it was restored.
+    fn restored_settings<Message, Theme>(
+        &mut self,
+        config: &Config<'_, Message, Theme>,
+    ) -> Option<(RowOptions, NameOrder)>
+    where
+        Theme: Catalog,
+    {
+        let restored = self.restored.as_mut()?;
+        let configured = (config.rows, config.order.locale());
+
+        if *restored.replaced.get_or_insert_with(|| configured.clone()) != configured {
+            self.restored = None;
+            return None;
+        }
+
+        let options = RowOptions {
+            show_hidden: restored.show_hidden,
+            show_extensions: restored.show_extensions,
+            ..config.rows
+        };
+
+        Some((options, restored.order.clone()))
+    }
+
+    /// Restores the expanded directories, selection, focus and sort and filter settings of a
+    /// snapshot, rebuilding the rows the next time the tree is synced.
+    pub fn restore(&mut self, snapshot: &FileTreeSnapshot) {
+        #[cfg(feature = "collation")]
+        let order = snapshot
+            .locale
+            .as_deref()
+            .and_then(|locale| locale.parse().ok())
+            .and_then(NameOrder::collated)
+            .unwrap_or_default();
+        #[cfg(not(feature = "collation"))]
+        let order = NameOrder::default();
+
+        self.open.clone_from(&snapshot.expanded);
+        self.selected.clone_from(&snapshot.selected);
+        self.focused.clone_from(&snapshot.focused);
+        self.restored = Some(Restored {
+            show_hidden: snapshot.show_hidden,
+            show_extensions: snapshot.show_extensions,
+            order,
+            replaced: None,
+        });
+        self.dirty = true;
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Whether a directory has no entries that would be shown in the tree.
+fn is_empty_dir(path: &Path, show_hidden: bool) -> bool {
+    std::fs::read_dir(path).map_or(true, |entries| {
+        !entries
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_ok_and(|t| t.is_file() || t.is_dir()))
+            .any(|entry| show_hidden || !entry.file_name().as_encoded_bytes().starts_with(b"."))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    type TestConfig<'a> = Config<'a, (), iced::Theme>;
+
+    /// Creates the given files in a temporary directory, and the given directories if they end
+    /// with a slash.
+    fn tree(paths: &[&str]) -> TempDir {
+        let dir = TempDir::new().unwrap();
+
+        for path in paths {
+            let path = dir.path().join(path);
+
+            if path.as_os_str().as_encoded_bytes().ends_with(b"/") {
+                fs::create_dir_all(path).unwrap();
+            } else {
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(path, "").unwrap();
+            }
+        }
+
+        dir
+    }
+
+    fn synced(root: &Path, config: &TestConfig<'_>) -> State {
+        let mut state = State::default();
+        state.sync(root, config);
+        state
+    }
+
+    /// Returns the names of the rows below the root, indented by their depth.
+    fn rows(state: &State) -> Vec<String> {
+        state.rows()[1..]
+            .iter()
+            .map(|row| format!("{}{}", "  ".repeat(row.depth - 1), row.name()))
+            .collect()
+    }
+
+    /// Carries out the running expansion, returning the directories it opened.
+    fn finish_expansion(state: &mut State, root: &Path, config: &TestConfig<'_>) -> Vec<PathBuf> {
+        while state.expansion.is_some() {
+            state.expand_step();
+        }
+
+        state.sync(root, config);
+
+        let mut opened = state
+            .take_toggled()
+            .into_iter()
+            .map(|(path, open)| {
+                assert!(open);
+                path.strip_prefix(root).unwrap().to_path_buf()
+            })
+            .collect::<Vec<_>>();
+        opened.sort();
+        opened
+    }
+
+    #[test]
+    fn rebuild_lists_directories_before_files() {
+        let dir = tree(&["b.txt", "a/", "c/d.txt", "A.txt"]);
+        let config = TestConfig::default();
+        let mut state = synced(dir.path(), &config);
+
+        assert!(rows(&state).is_empty());
+
+        state.toggle(0);
+
+        assert_eq!(rows(&state), ["a", "c", "A.txt", "b.txt"]);
+        assert!(state.entry(1).is_empty());
+        assert!(!state.entry(2).is_empty());
+
+        let c = state.row_of(&dir.path().join("c")).unwrap();
+        state.toggle(c);
+
+        assert_eq!(rows(&state), ["a", "c", "  d.txt", "A.txt", "b.txt"]);
+        assert_eq!(state.rows()[c].end, c + 2);
+        assert_eq!(state.rows()[c + 1].parent, Some(c));
+    }
+
+    #[test]
+    fn rebuild_keeps_the_root_entry() {
+        let dir = tree(&["a/b.txt"]);
+        let config = TestConfig::default();
+        let mut state = synced(dir.path(), &config);
+        let root = state.rows()[0].entry.clone();
+
+        state.toggle(0);
+
+        assert!(Rc::ptr_eq(&state.rows()[0].entry, &root));
+
+        let config = TestConfig {
+            rows: RowOptions {
+                show_hidden: true,
+                ..RowOptions::default()
+            },
+            ..TestConfig::default()
+        };
+        state.sync(dir.path(), &config);
+
+        assert!(!Rc::ptr_eq(&state.rows()[0].entry, &root));
+    }
+
+    #[test]
+    fn hidden_entries_are_only_shown_if_enabled() {
+        let dir = tree(&[".git/HEAD", "a/.keep", ".env", "b.txt"]);
+        let config = TestConfig::default();
+        let mut state = synced(dir.path(), &config);
+
+        state.toggle(0);
+
+        assert_eq!(rows(&state), ["a", "b.txt"]);
+        assert!(state.entry(1).is_empty());
+
+        let config = TestConfig {
+            rows: RowOptions {
+                show_hidden: true,
+                ..RowOptions::default()
+            },
+            ..TestConfig::default()
+        };
+        state.sync(dir.path(), &config);
+
+        assert_eq!(rows(&state), [".git", "a", ".env", "b.txt"]);
+        assert!(!state.entry(2).is_empty());
+    }
+
+    #[test]
+    fn refresh_reads_changed_directories() {
+        let dir = tree(&["a/", "b.txt"]);
+        let config = TestConfig::default();
+        let mut state = synced(dir.path(), &config);
+
+        state.toggle(0);
+        fs::write(dir.path().join("a/c.txt"), "").unwrap();
+        fs::remove_file(dir.path().join("b.txt")).unwrap();
+        state.sync(dir.path(), &config);
+
+        assert_eq!(rows(&state), ["a", "b.txt"]);
+        assert!(state.entry(1).is_empty());
+
+        state.refresh();
+        state.sync(dir.path(), &config);
+
+        assert_eq!(rows(&state), ["a"]);
+        assert!(!state.entry(1).is_empty());
+    }
+
+    #[test]
+    fn expand_opens_everything() {
+        let dir = tree(&["a/b/c/d.txt", "a/e.txt", "f/"]);
+        let config = TestConfig::default();
+        let mut state = synced(dir.path(), &config);
+
+        state.expand(dir.path(), Limit::Unlimited);
+
+        assert_eq!(
+            finish_expansion(&mut state, dir.path(), &config),
+            [
+                Path::new(""),
+                Path::new("a"),
+                Path::new("a/b"),
+                Path::new("a/b/c"),
+                Path::new("f")
+            ]
+        );
+        assert_eq!(
+            rows(&state),
+            ["a", "  b", "    c", "      d.txt", "  e.txt", "f"]
+        );
+    }
+
+    #[test]
+    fn expand_stops_at_the_depth() {
+        let dir = tree(&["a/b/c/d.txt", "e/f/"]);
+        let config = TestConfig::default();
+        let mut state = synced(dir.path(), &config);
+
+        state.expand(dir.path(), Limit::Depth(2));
+
+        assert_eq!(
+            finish_expansion(&mut state, dir.path(), &config),
+            [Path::new(""), Path::new("a"), Path::new("e")]
+        );
+        assert_eq!(rows(&state), ["a", "  b", "e", "  f"]);
+    }
+
+    #[test]
+    fn expand_stops_at_the_first_directory_over_budget() {
+        let dir = tree(&["a/1", "a/2", "b/1", "b/2", "b/3", "c/1"]);
+        let config = TestConfig::default();
+        let mut state = synced(dir.path(), &config);
+
+        // the root and `a` fit, `b` doesn't, and `c` isn't opened so the tree stays even
+        state.expand(dir.path(), Limit::Entries(6));
+
+        assert_eq!(
+            finish_expansion(&mut state, dir.path(), &config),
+            [Path::new(""), Path::new("a")]
+        );
+    }
+
+    #[test]
+    fn expand_opens_compact_folders_at_once() {
+        let dir = tree(&["a/b/c/d.txt", "a/b/e/", "f.txt"]);
+        let config = TestConfig {
+            rows: RowOptions {
+                compact_folders: true,
+                ..RowOptions::default()
+            },
+            ..TestConfig::default()
+        };
+        let mut state = synced(dir.path(), &config);
+
+        state.expand(dir.path(), Limit::Depth(2));
+
+        assert_eq!(
+            finish_expansion(&mut state, dir.path(), &config),
+            [Path::new(""), Path::new("a"), Path::new("a/b")]
+        );
+        assert_eq!(rows(&state), ["a/b", "  c", "  e", "f.txt"]);
+
+        state.collapse_all();
+        state.sync(dir.path(), &config);
+        state.take_toggled();
+
+        // the chain is charged for the entries of its last directory
+        state.expand(dir.path(), Limit::Entries(4));
+
+        assert_eq!(
+            finish_expansion(&mut state, dir.path(), &config),
+            [Path::new("a"), Path::new("a/b")]
+        );
+    }
+
+    #[test]
+    fn expand_opens_closed_ancestors() {
+        let dir = tree(&["a/b/c/d.txt", "e/"]);
+        let config = TestConfig::default();
+        let mut state = synced(dir.path(), &config);
+
+        state.expand(&dir.path().join("a/b"), Limit::Unlimited);
+
+        assert_eq!(
+            finish_expansion(&mut state, dir.path(), &config),
+            [
+                Path::new(""),
+                Path::new("a"),
+                Path::new("a/b"),
+                Path::new("a/b/c")
+            ]
+        );
+        assert_eq!(rows(&state), ["a", "  b", "    c", "      d.txt", "e"]);
+    }
+
+    #[test]
+    fn cancel_expansion_keeps_opened_directories() {
+        let paths = (0..EXPANSION_STEP * 2)
+            .map(|i| format!("{i:03}/"))
+            .collect::<Vec<_>>();
+        let dir = tree(&paths.iter().map(String::as_str).collect::<Vec<_>>());
+        let config = TestConfig::default();
+        let mut state = synced(dir.path(), &config);
+
+        state.expand(dir.path(), Limit::Unlimited);
+        state.expand_step();
+        state.cancel_expansion();
+
+        assert!(state.expansion.is_none());
+        assert_eq!(
+            finish_expansion(&mut state, dir.path(), &config).len(),
+            EXPANSION_STEP
+        );
+    }
+
+    #[test]
+    fn collapse_siblings_keeps_the_entry_and_its_children() {
+        let dir = tree(&["a/b/c.txt", "d/e.txt", "f/g.txt"]);
+        let config = TestConfig::default();
+        let mut state = synced(dir.path(), &config);
+
+        state.expand(dir.path(), Limit::Unlimited);
+        finish_expansion(&mut state, dir.path(), &config);
+        state.collapse_siblings(&dir.path().join("a"));
+        state.sync(dir.path(), &config);
+
+        assert_eq!(rows(&state), ["a", "  b", "    c.txt", "d", "f"]);
+        assert_eq!(state.take_toggled().len(), 2);
+
+        state.collapse_all();
+        state.sync(dir.path(), &config);
+
+        assert_eq!(rows(&state), ["a", "d", "f"]);
+    }
+
+    #[test]
+    fn entries_only_move_into_other_directories() {
+        let entry = Entry::new(PathBuf::from("/a/b"), true, RowOptions::default());
+
+        assert!(entry.moves_into(Path::new("/")));
+        assert!(entry.moves_into(Path::new("/c")));
+        assert!(entry.moves_into(Path::new("/a/bc")));
+        assert!(!entry.moves_into(Path::new("/a")));
+        assert!(!entry.moves_into(Path::new("/a/b")));
+        assert!(!entry.moves_into(Path::new("/a/b/c")));
+    }
+
+    #[test]
+    fn reveal_opens_ancestors_and_selects() {
+        let dir = tree(&["a/b/c.txt", "d.txt"]);
+        let config = TestConfig::default();
+        let mut state = synced(dir.path(), &config);
+        let path = dir.path().join("a/b/c.txt");
+
+        state.reveal(&path);
+        state.sync(dir.path(), &config);
+
+        assert_eq!(rows(&state), ["a", "  b", "    c.txt", "d.txt"]);
+        assert_eq!(state.selected_row(), state.row_of(&path));
+        assert_eq!(state.take_toggled().len(), 3);
+    }
+
+    #[test]
+    fn focus_starts_at_the_selection() {
+        let dir = tree(&["a.txt", "b.txt"]);
+        let config = TestConfig::default();
+        let mut state = synced(dir.path(), &config);
+
+        state.focus();
+
+        assert!(state.is_focused(0));
+
+        state.toggle(0);
+        state.select(2, &dir.path().join("b.txt"));
+        state.unfocus();
+
+        assert!(!state.is_focused(2));
+        assert_eq!(state.focused_row(), None);
+
+        state.focus();
+
+        assert_eq!(state.focused_row(), Some(2));
+    }
+
+    #[test]
+    fn controlled_mode_follows_the_application() {
+        let dir = tree(&["a/b.txt", "c/d.txt"]);
+        let mut expanded = HashSet::from([dir.path().to_path_buf()]);
+        let mut state = State::default();
+
+        state.sync(
+            dir.path(),
+            &TestConfig {
+                expanded: Some(&expanded),
+                ..TestConfig::default()
+            },
+        );
+
+        assert_eq!(rows(&state), ["a", "c"]);
+
+        // operations are applied until the application has been told about them
+        state.reveal(&dir.path().join("c/d.txt"));
+        state.sync(
+            dir.path(),
+            &TestConfig {
+                expanded: Some(&expanded),
+                ..TestConfig::default()
+            },
+        );
+
+        assert_eq!(rows(&state), ["a", "c", "  d.txt"]);
+
+        for (path, open) in state.take_toggled() {
+            assert!(open);
+            expanded.insert(path);
+        }
+
+        expanded.insert(dir.path().join("a"));
+        state.sync(
+            dir.path(),
+            &TestConfig {
+                expanded: Some(&expanded),
+                ..TestConfig::default()
+            },
+        );
+
+        assert_eq!(rows(&state), ["a", "  b.txt", "c", "  d.txt"]);
+    }
+
+    #[cfg(feature = "serde")]
+    fn snapshot_of(root: &Path) -> FileTreeSnapshot {
+        FileTreeSnapshot {
+            expanded: HashSet::from([root.to_path_buf(), root.join("a")]),
+            selected: Some(root.join("a/b.txt")),
+            focused: Some(root.join("a/b.txt")),
+            scroll_anchor: Some(root.to_path_buf()),
+            show_hidden: true,
+            show_extensions: true,
+            locale: None,
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn restore_applies_the_snapshot() {
+        let dir = tree(&[".env", "a/b.txt", "c.txt"]);
+        let config = TestConfig::default();
+        let mut state = synced(dir.path(), &config);
+
+        state.restore(&snapshot_of(dir.path()));
+        state.sync(dir.path(), &config);
+
+        assert_eq!(rows(&state), ["a", "  b.txt", ".env", "c.txt"]);
+        assert_eq!(state.selected_row(), Some(2));
+        assert_eq!(state.focused_row(), Some(2));
+
+        // the restored settings stay while the application keeps its own
+        state.sync(dir.path(), &config);
+
+        assert_eq!(rows(&state), ["a", "  b.txt", ".env", "c.txt"]);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn changing_the_config_drops_restored_settings() {
+        let dir = tree(&[".env", "a/b.txt", "c.txt"]);
+        let config = TestConfig::default();
+        let mut state = synced(dir.path(), &config);
+
+        state.restore(&snapshot_of(dir.path()));
+        state.sync(dir.path(), &config);
+
+        let changed = TestConfig {
+            rows: RowOptions {
+                show_extensions: false,
+                ..RowOptions::default()
+            },
+            ..TestConfig::default()
+        };
+        state.sync(dir.path(), &changed);
+
+        assert_eq!(rows(&state), ["a", "  b", "c"]);
+
+        // going back to the previous settings doesn't bring the restored ones back
+        state.sync(dir.path(), &config);
+
+        assert_eq!(rows(&state), ["a", "  b.txt", "c.txt"]);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn snapshot_round_trips_through_restore() {
+        let dir = tree(&[".env", "a/b.txt", "c.txt"]);
+        let config = TestConfig::default();
+        let snapshot = snapshot_of(dir.path());
+        let mut state = synced(dir.path(), &config);
+
+        state.restore(&snapshot);
+        state.sync(dir.path(), &config);
+
+        assert_eq!(state.snapshot(), snapshot);
+
+        let json = serde_json::to_string(&state.snapshot()).unwrap();
+        let mut restored = synced(dir.path(), &config);
+
+        restored.restore(&serde_json::from_str(&json).unwrap());
+        restored.sync(dir.path(), &config);
+
+        assert_eq!(restored.snapshot(), snapshot);
+        assert_eq!(rows(&restored), rows(&state));
+    }
+}
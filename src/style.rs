@@ -0,0 +1,160 @@
+//! Change the appearance of a [`FileTree`](crate::FileTree).
+use iced::{Background, Border, Color, Theme};
+
+/// The possible status of a row in a [`FileTree`](crate::FileTree).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Status {
+    /// The row is neither hovered nor selected.
+    Idle,
+    /// The row is being hovered.
+    Hovered,
+    /// The row is selected.
+    Selected,
+    /// The row is selected and has keyboard focus.
+    Focused,
+    /// Something is being dragged over the row and would be dropped into it.
+    DropTarget,
+}
+
+/// The appearance of a row in a [`FileTree`](crate::FileTree).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Style {
+    /// The [`Background`] of the row.
+    pub background: Option<Background>,
+    /// The [`Border`] of the row.
+    pub border: Border,
+    /// The text [`Color`] of the row.
+    pub text_color: Color,
+    /// The [`Color`] of the icons and chevrons of the row.
+    pub icon_color: Color,
+    /// The [`Color`] of the indentation guides below a directory.
+    pub guide_color: Color,
+    /// The [`Color`] of the indentation guide below the directory containing the hovered or
+    /// selected row.
+    ///
+    /// Only used with [`Guides::Active`](crate::Guides::Active) and
+    /// [`Guides::Highlighted`](crate::Guides::Highlighted).
+    pub active_guide_color: Color,
+}
+
+/// The appearance of the tooltip of a [`FileTree`](crate::FileTree), see
+/// [`FileTree::tooltip`](crate::FileTree::tooltip).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TooltipStyle {
+    /// The [`Background`] of the tooltip.
+    pub background: Option<Background>,
+    /// The [`Border`] of the tooltip.
+    pub border: Border,
+    /// The text [`Color`] of the tooltip.
+    pub text_color: Color,
+}
+
+/// The theme catalog of a [`FileTree`](crate::FileTree).
+pub trait Catalog {
+    /// The item class of the [`Catalog`].
+    type Class<'a>;
+
+    /// The item class of the tooltips of the [`Catalog`].
+    type TooltipClass<'a>;
+
+    /// The default class produced by the [`Catalog`].
+    fn default<'a>() -> Self::Class<'a>;
+
+    /// The default tooltip class produced by the [`Catalog`].
+    fn default_tooltip<'a>() -> Self::TooltipClass<'a>;
+
+    /// The [`Style`] of a class with the given status.
+    fn style(&self, class: &Self::Class<'_>, status: Status) -> Style;
+
+    /// The [`TooltipStyle`] of a tooltip class.
+    fn tooltip(&self, class: &Self::TooltipClass<'_>) -> TooltipStyle;
+}
+
+/// A styling function for a [`FileTree`](crate::FileTree).
+pub type StyleFn<'a, Theme> = Box<dyn Fn(&Theme, Status) -> Style + 'a>;
+
+/// A styling function for the tooltip of a [`FileTree`](crate::FileTree).
+pub type TooltipStyleFn<'a, Theme> = Box<dyn Fn(&Theme) -> TooltipStyle + 'a>;
+
+impl Catalog for Theme {
+    type Class<'a> = StyleFn<'a, Self>;
+    type TooltipClass<'a> = TooltipStyleFn<'a, Self>;
+
+    fn default<'a>() -> Self::Class<'a> {
+        Box::new(default)
+    }
+
+    fn default_tooltip<'a>() -> Self::TooltipClass<'a> {
+        Box::new(tooltip)
+    }
+
+    fn style(&self, class: &Self::Class<'_>, status: Status) -> Style {
+        class(self, status)
+    }
+
+    fn tooltip(&self, class: &Self::TooltipClass<'_>) -> TooltipStyle {
+        class(self)
+    }
+}
+
+/// The default style of a [`FileTree`](crate::FileTree).
+#[must_use]
+pub fn default(theme: &Theme, status: Status) -> Style {
+    let palette = theme.extended_palette();
+
+    let base = Style {
+        background: Some(palette.primary.weak.color.into()),
+        border: Border::default(),
+        text_color: palette.secondary.base.text,
+        icon_color: palette.secondary.base.text,
+        guide_color: palette.primary.weak.color,
+        active_guide_color: palette.primary.strong.color,
+    };
+
+    match status {
+        Status::Idle => base,
+        Status::Hovered => Style {
+            background: Some(palette.secondary.weak.color.into()),
+            ..base
+        },
+        Status::Selected => Style {
+            background: Some(palette.primary.base.color.into()),
+            text_color: palette.primary.base.text,
+            icon_color: palette.primary.base.text,
+            ..base
+        },
+        Status::Focused => Style {
+            background: Some(palette.primary.base.color.into()),
+            border: Border {
+                color: palette.primary.strong.color,
+                width: 1.0,
+                radius: 0.0.into(),
+            },
+            text_color: palette.primary.base.text,
+            icon_color: palette.primary.base.text,
+            ..base
+        },
+        Status::DropTarget => Style {
+            background: Some(palette.secondary.base.color.into()),
+            text_color: palette.secondary.base.text,
+            icon_color: palette.secondary.base.text,
+            ..base
+        },
+    }
+}
+
+/// The default style of the tooltip of a [`FileTree`](crate::FileTree).
+#[must_use]
+pub fn tooltip(theme: &Theme) -> TooltipStyle {
+    let palette = theme.extended_palette();
+
+    TooltipStyle {
+        background: Some(palette.background.strong.color.into()),
+        border: Border {
+            color: palette.background.weak.color,
+            width: 1.0,
+            radius: 2.0.into(),
+        },
+        text_color: palette.background.strong.text,
+    }
+}
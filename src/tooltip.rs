@@ -0,0 +1,285 @@
+use crate::style::Catalog;
+use iced::{
+    advanced::{
+        layout::{Layout, Node},
+        mouse::Cursor,
+        overlay::Overlay,
+        renderer::{self, Quad},
+        text::{self, LineHeight, Paragraph as _, Shaping, Wrapping},
+        Text,
+    },
+    alignment::{Horizontal, Vertical},
+    Font, Padding, Pixels, Point, Rectangle, Size,
+};
+use std::{
+    fs::Metadata,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const PADDING: f32 = 4.0;
+
+/// The tooltip shown below the hovered row of a [`FileTree`](crate::FileTree).
+pub struct Tooltip<'a, 'b, Theme>
+where
+    Theme: Catalog,
+{
+    pub content: &'a str,
+    /// The bounds of the hovered row, in absolute coordinates.
+    pub anchor: Rectangle,
+    pub text_size: Pixels,
+    pub font: Font,
+    pub class: &'a Theme::TooltipClass<'b>,
+}
+
+impl<Theme> Tooltip<'_, '_, Theme>
+where
+    Theme: Catalog,
+{
+    fn text(&self, bounds: Size) -> Text<&str, Font> {
+        Text {
+            content: self.content,
+            bounds,
+            size: self.text_size,
+            line_height: LineHeight::default(),
+            font: self.font,
+            horizontal_alignment: Horizontal::Left,
+            vertical_alignment: Vertical::Top,
+            shaping: Shaping::Advanced,
+            wrapping: Wrapping::WordOrGlyph,
+        }
+    }
+}
+
+impl<Message, Theme, Renderer> Overlay<Message, Theme, Renderer> for Tooltip<'_, '_, Theme>
+where
+    Theme: Catalog,
+    Renderer: text::Renderer<Font = Font>,
+{
+    fn layout(&mut self, _renderer: &Renderer, bounds: Size) -> Node {
+        let max_width = (bounds.width - Padding::new(PADDING).horizontal()).max(0.0);
+        let paragraph =
+            Renderer::Paragraph::with_text(self.text(Size::new(max_width, f32::INFINITY)));
+        let size = paragraph.min_bounds().expand(Padding::new(PADDING));
+
+        // keep the tooltip inside the window, flipping it above the row if there's no room below
+        let x = self.anchor.x.min(bounds.width - size.width).max(0.0);
+        let y = if self.anchor.y + self.anchor.height + size.height > bounds.height {
+            (self.anchor.y - size.height).max(0.0)
+        } else {
+            self.anchor.y + self.anchor.height
+        };
+
+        Node::new(size).move_to(Point::new(x, y))
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor: Cursor,
+    ) {
+        let bounds = layout.bounds();
+        let style = theme.tooltip(self.class);
+
+        if let Some(background) = style.background {
+            renderer.fill_quad(
+                Quad {
+                    bounds,
+                    border: style.border,
+                    ..Quad::default()
+                },
+                background,
+            );
+        }
+
+        let text = self.text(bounds.shrink(PADDING).size());
+
+        renderer.fill_text(
+            Text {
+                content: text.content.to_owned(),
+                bounds: text.bounds,
+                size: text.size,
+                line_height: text.line_height,
+                font: text.font,
+                horizontal_alignment: text.horizontal_alignment,
+                vertical_alignment: text.vertical_alignment,
+                shaping: text.shaping,
+                wrapping: text.wrapping,
+            },
+            bounds.shrink(PADDING).position(),
+            style.text_color,
+            bounds,
+        );
+    }
+
+    fn is_over(&self, _layout: Layout<'_>, _renderer: &Renderer, _cursor_position: Point) -> bool {
+        // the tooltip shouldn't take the hover away from the row below it
+        false
+    }
+}
+
+/// Describes an entry with its full path, size, modification time and permissions, for use with
+/// [`FileTree::tooltip`](crate::FileTree::tooltip).
+#[must_use]
+pub fn describe(path: &Path, metadata: &Metadata) -> String {
+    let mut lines = vec![path.display().to_string()];
+
+    if metadata.is_file() {
+        lines.push(format!("Size: {}", format_size(metadata.len())));
+    }
+
+    if let Ok(modified) = metadata.modified() {
+        lines.push(format!("Modified: {}", format_time(modified)));
+    }
+
+    lines.push(format!("Permissions: {}", format_permissions(metadata)));
+
+    lines.join("\n")
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} bytes");
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+/// Formats a time as a UTC date and time, without pulling in a date library.
+fn format_time(time: SystemTime) -> String {
+    let Ok(since_epoch) = time.duration_since(UNIX_EPOCH) else {
+        return "unknown".to_owned();
+    };
+
+    let seconds = since_epoch.as_secs();
+    let (days, seconds) = (seconds / 86400, seconds % 86400);
+
+    // https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02} UTC",
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}
+
+#[cfg(unix)]
+fn format_permissions(metadata: &Metadata) -> String {
+    use std::os::unix::fs::PermissionsExt as _;
+
+    format_mode(metadata.permissions().mode())
+}
+
+/// Formats the permission bits of a Unix file mode like `ls -l`, e.g. `rwxr-xr-x`.
+#[cfg(unix)]
+fn format_mode(mode: u32) -> String {
+    (0..9)
+        .map(|bit| {
+            if mode & (0o400 >> bit) == 0 {
+                '-'
+            } else {
+                ['r', 'w', 'x'][bit % 3]
+            }
+        })
+        .collect()
+}
+
+#[cfg(not(unix))]
+fn format_permissions(metadata: &Metadata) -> String {
+    if metadata.permissions().readonly() {
+        "read-only".to_owned()
+    } else {
+        "read-write".to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn sizes() {
+        for (bytes, formatted) in [
+            (0, "0 bytes"),
+            (1023, "1023 bytes"),
+            (1024, "1.0 KiB"),
+            (1536, "1.5 KiB"),
+            (1024 * 1024 - 1, "1024.0 KiB"),
+            (1024 * 1024, "1.0 MiB"),
+            (5 * 1024 * 1024 * 1024, "5.0 GiB"),
+            (1 << 40, "1.0 TiB"),
+            (1 << 50, "1024.0 TiB"),
+        ] {
+            assert_eq!(format_size(bytes), formatted, "{bytes} bytes");
+        }
+    }
+
+    #[test]
+    fn times() {
+        for (seconds, formatted) in [
+            (0, "1970-01-01 00:00 UTC"),
+            (59, "1970-01-01 00:00 UTC"),
+            (68_214_840, "1972-02-29 12:34 UTC"),
+            (946_684_799, "1999-12-31 23:59 UTC"),
+            (951_782_400, "2000-02-29 00:00 UTC"),
+            (951_868_800, "2000-03-01 00:00 UTC"),
+            (1_709_251_140, "2024-02-29 23:59 UTC"),
+            (4_107_456_000, "2100-02-28 00:00 UTC"),
+            (4_107_542_400, "2100-03-01 00:00 UTC"),
+        ] {
+            assert_eq!(
+                format_time(UNIX_EPOCH + Duration::from_secs(seconds)),
+                formatted,
+                "{seconds} seconds"
+            );
+        }
+    }
+
+    #[test]
+    fn times_before_the_epoch() {
+        assert_eq!(format_time(UNIX_EPOCH - Duration::from_secs(1)), "unknown");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn modes() {
+        for (mode, formatted) in [
+            (0o000, "---------"),
+            (0o644, "rw-r--r--"),
+            (0o755, "rwxr-xr-x"),
+            (0o777, "rwxrwxrwx"),
+            (0o100_640, "rw-r-----"),
+            (0o4_711, "rwx--x--x"),
+        ] {
+            assert_eq!(format_mode(mode), formatted, "{mode:o}");
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use crate::Truncation;
+use iced::{
+    advanced::text::{self, LineHeight, Paragraph as _, Shaping, Text, Wrapping},
+    alignment::{Horizontal, Vertical},
+    Font, Pixels, Size,
+};
+use std::iter::once;
+use unicode_segmentation::UnicodeSegmentation as _;
+
+const ELLIPSIS: char = '\u{2026}';
+
+/// Shortens a single line of text that doesn't fit in the given width with an ellipsis until it
+/// does.
+pub fn truncate<Renderer>(
+    content: &str,
+    font: Font,
+    size: Pixels,
+    truncation: Truncation,
+    max_width: f32,
+) -> String
+where
+    Renderer: text::Renderer<Font = Font>,
+{
+    shorten(content, truncation, |content| {
+        measure::<Renderer>(content, font, size) <= max_width
+    })
+}
+
+/// Shortens a name that doesn't fit with an ellipsis, keeping as many grapheme clusters as `fits`
+/// allows, so accented letters, emoji sequences and flags are never split.
+fn shorten(name: &str, truncation: Truncation, fits: impl Fn(&str) -> bool) -> String {
+    if truncation == Truncation::Clip {
+        return name.to_owned();
+    }
+
+    let boundaries = name
+        .grapheme_indices(true)
+        .map(|(i, _)| i)
+        .chain(once(name.len()))
+        .collect::<Vec<_>>();
+    let graphemes = boundaries.len() - 1;
+
+    let extension = match truncation {
+        Truncation::Middle => name
+            .rfind('.')
+            .filter(|&i| i > 0)
+            .map_or(0, |i| name[i..].graphemes(true).count()),
+        _ => 0,
+    };
+
+    // the name with all but the given number of grapheme clusters replaced by an ellipsis
+    let shortened = |kept: usize| match truncation {
+        Truncation::Middle => {
+            let tail = extension.max(kept / 2).min(kept);
+            let head = kept - tail;
+
+            format!(
+                "{}{ELLIPSIS}{}",
+                &name[..boundaries[head]],
+                &name[boundaries[graphemes - tail]..]
+            )
+        }
+        _ => format!("{}{ELLIPSIS}", &name[..boundaries[kept]]),
+    };
+
+    let (mut low, mut high) = (0, graphemes.saturating_sub(1));
+
+    while low < high {
+        let mid = (low + high).div_ceil(2);
+
+        if fits(&shortened(mid)) {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+
+    shortened(low)
+}
+
+/// Returns the width of a single line of text.
+pub fn measure<Renderer>(content: &str, font: Font, size: Pixels) -> f32
+where
+    Renderer: text::Renderer<Font = Font>,
+{
+    Renderer::Paragraph::with_text(Text {
+        content,
+        bounds: Size::INFINITY,
+        size,
+        line_height: LineHeight::default(),
+        font,
+        horizontal_alignment: Horizontal::Left,
+        vertical_alignment: Vertical::Center,
+        shaping: Shaping::Advanced,
+        wrapping: Wrapping::None,
+    })
+    .min_width()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shorten_to(name: &str, truncation: Truncation, max_graphemes: usize) -> String {
+        shorten(name, truncation, |content| {
+            content.graphemes(true).count() <= max_graphemes
+        })
+    }
+
+    #[test]
+    fn clip_keeps_the_name() {
+        assert_eq!(shorten_to("abcdefghij", Truncation::Clip, 5), "abcdefghij");
+    }
+
+    #[test]
+    fn end_keeps_the_start() {
+        assert_eq!(shorten_to("abcdefghij", Truncation::End, 5), "abcd\u{2026}");
+        assert_eq!(shorten_to("notes.txt", Truncation::End, 6), "notes\u{2026}");
+    }
+
+    #[test]
+    fn middle_keeps_both_ends() {
+        assert_eq!(
+            shorten_to("abcdefghij", Truncation::Middle, 5),
+            "ab\u{2026}ij"
+        );
+        assert_eq!(
+            shorten_to("abcdefghij", Truncation::Middle, 6),
+            "abc\u{2026}ij"
+        );
+    }
+
+    #[test]
+    fn middle_keeps_the_extension() {
+        assert_eq!(
+            shorten_to("long_file_name.txt", Truncation::Middle, 10),
+            "long_\u{2026}.txt"
+        );
+        assert_eq!(
+            shorten_to("abcdefghijklmnop.rs", Truncation::Middle, 12),
+            "abcdef\u{2026}op.rs"
+        );
+    }
+
+    #[test]
+    fn middle_shortens_long_extensions() {
+        assert_eq!(
+            shorten_to("a.verylongextension", Truncation::Middle, 4),
+            "\u{2026}ion"
+        );
+    }
+
+    #[test]
+    fn middle_ignores_leading_dots() {
+        assert_eq!(
+            shorten_to(".bashrc_backup", Truncation::Middle, 7),
+            ".ba\u{2026}kup"
+        );
+    }
+
+    #[test]
+    fn keeps_only_the_ellipsis_if_nothing_fits() {
+        assert_eq!(shorten_to("abc", Truncation::End, 0), "\u{2026}");
+        assert_eq!(shorten_to("abc", Truncation::Middle, 0), "\u{2026}");
+        assert_eq!(shorten_to("", Truncation::End, 0), "\u{2026}");
+    }
+
+    #[test]
+    fn respects_char_boundaries() {
+        assert_eq!(
+            shorten_to("\u{e9}\u{e9}\u{e9}\u{e9}\u{e9}", Truncation::End, 3),
+            "\u{e9}\u{e9}\u{2026}"
+        );
+        assert_eq!(
+            shorten_to(
+                "\u{e9}\u{e9}\u{e9}\u{e9}\u{e9}.\u{fc}",
+                Truncation::Middle,
+                5
+            ),
+            "\u{e9}\u{e9}\u{2026}.\u{fc}"
+        );
+    }
+
+    #[test]
+    fn respects_grapheme_boundaries() {
+        // an `e` followed by a combining acute accent
+        let accented = "e\u{301}".repeat(5);
+        assert_eq!(
+            shorten_to(&accented, Truncation::End, 3),
+            "e\u{301}e\u{301}\u{2026}"
+        );
+
+        // a family emoji joined with zero width joiners
+        let family = "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}";
+        assert_eq!(
+            shorten_to(&family.repeat(3), Truncation::End, 2),
+            format!("{family}\u{2026}")
+        );
+
+        // the flags of Germany, France and Japan
+        assert_eq!(
+            shorten_to(
+                "\u{1f1e9}\u{1f1ea}\u{1f1eb}\u{1f1f7}\u{1f1ef}\u{1f1f5}",
+                Truncation::Middle,
+                3
+            ),
+            "\u{1f1e9}\u{1f1ea}\u{2026}\u{1f1ef}\u{1f1f5}"
+        );
+    }
+}